@@ -3,6 +3,7 @@ extern crate gfx_window_glutin;
 extern crate glutin;
 extern crate imgui;
 extern crate imgui_gfx_renderer;
+extern crate imgui_winit_support;
 
 use imgui::*;
 
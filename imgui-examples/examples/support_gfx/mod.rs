@@ -1,13 +1,6 @@
-use imgui::{ImGui, Ui};
+use imgui::{FontConfig, ImGui, Ui};
 use imgui_gfx_renderer::{Renderer, Shaders};
-use std::time::Instant;
-
-#[derive(Copy, Clone, PartialEq, Debug, Default)]
-struct MouseState {
-    pos: (i32, i32),
-    pressed: (bool, bool, bool),
-    wheel: f32,
-}
+use imgui_winit_support::{HiDpiMode, WinitPlatform};
 
 pub fn run<F: FnMut(&Ui) -> bool>(title: String, clear_color: [f32; 4], mut run_ui: F) {
     use gfx::{self, Device};
@@ -17,12 +10,11 @@ pub fn run<F: FnMut(&Ui) -> bool>(title: String, clear_color: [f32; 4], mut run_
     type ColorFormat = gfx::format::Rgba8;
     type DepthFormat = gfx::format::DepthStencil;
 
-
     let mut events_loop = glutin::EventsLoop::new();
     let context = glutin::ContextBuilder::new().with_vsync(true);
     let window = glutin::WindowBuilder::new()
         .with_title(title)
-        .with_dimensions(1024, 768);
+        .with_dimensions(glutin::dpi::LogicalSize::new(1024.0, 768.0));
     let (window, mut device, mut factory, mut main_color, mut main_depth) =
         gfx_window_glutin::init::<ColorFormat, DepthFormat>(window, context, &events_loop);
     let mut encoder: gfx::Encoder<_, _> = factory.create_command_buffer().into();
@@ -44,105 +36,54 @@ pub fn run<F: FnMut(&Ui) -> bool>(title: String, clear_color: [f32; 4], mut run_
     };
 
     let mut imgui = ImGui::init();
+    imgui.set_ini_filename(None);
+
+    let mut platform = WinitPlatform::init();
+    platform.attach_window(window.window(), HiDpiMode::Default);
+    platform.attach_ime(&mut imgui);
+
+    let mut hidpi_factor = platform.hidpi_factor();
+    configure_fonts(&mut imgui, hidpi_factor);
     let mut renderer = Renderer::init(&mut imgui, &mut factory, shaders, main_color.clone())
         .expect("Failed to initialize renderer");
 
-    configure_keys(&mut imgui);
-
-    let mut last_frame = Instant::now();
-    let mut mouse_state = MouseState::default();
     let mut quit = false;
 
     loop {
         events_loop.poll_events(|event| {
-            use glutin::WindowEvent::*;
-            use glutin::ElementState::Pressed;
-            use glutin::{Event, MouseButton, MouseScrollDelta, TouchPhase};
+            use glutin::{Event, WindowEvent};
 
+            platform.handle_event(&mut imgui, &event);
             if let Event::WindowEvent { event, .. } = event {
                 match event {
-                    Resized(_, _) => {
+                    WindowEvent::Resized(_) => {
                         gfx_window_glutin::update_views(&window, &mut main_color, &mut main_depth);
                         renderer.update_render_target(main_color.clone());
                     }
-                    Closed => quit = true,
-                    KeyboardInput { input, .. } => {
-                        use glutin::VirtualKeyCode as Key;
-
-                        let pressed = input.state == Pressed;
-                        match input.virtual_keycode {
-                            Some(Key::Tab) => imgui.set_key(0, pressed),
-                            Some(Key::Left) => imgui.set_key(1, pressed),
-                            Some(Key::Right) => imgui.set_key(2, pressed),
-                            Some(Key::Up) => imgui.set_key(3, pressed),
-                            Some(Key::Down) => imgui.set_key(4, pressed),
-                            Some(Key::PageUp) => imgui.set_key(5, pressed),
-                            Some(Key::PageDown) => imgui.set_key(6, pressed),
-                            Some(Key::Home) => imgui.set_key(7, pressed),
-                            Some(Key::End) => imgui.set_key(8, pressed),
-                            Some(Key::Delete) => imgui.set_key(9, pressed),
-                            Some(Key::Back) => imgui.set_key(10, pressed),
-                            Some(Key::Return) => imgui.set_key(11, pressed),
-                            Some(Key::Escape) => imgui.set_key(12, pressed),
-                            Some(Key::A) => imgui.set_key(13, pressed),
-                            Some(Key::C) => imgui.set_key(14, pressed),
-                            Some(Key::V) => imgui.set_key(15, pressed),
-                            Some(Key::X) => imgui.set_key(16, pressed),
-                            Some(Key::Y) => imgui.set_key(17, pressed),
-                            Some(Key::Z) => imgui.set_key(18, pressed),
-                            Some(Key::LControl) |
-                            Some(Key::RControl) => imgui.set_key_ctrl(pressed),
-                            Some(Key::LShift) |
-                            Some(Key::RShift) => imgui.set_key_shift(pressed),
-                            Some(Key::LAlt) | Some(Key::RAlt) => imgui.set_key_alt(pressed),
-                            Some(Key::LWin) | Some(Key::RWin) => imgui.set_key_super(pressed),
-                            _ => {}
-                        }
-                    }
-                    CursorMoved { position: (x, y), .. } => mouse_state.pos = (x as i32, y as i32),
-                    MouseInput { state, button, .. } => {
-                        match button {
-                            MouseButton::Left => mouse_state.pressed.0 = state == Pressed,
-                            MouseButton::Right => mouse_state.pressed.1 = state == Pressed,
-                            MouseButton::Middle => mouse_state.pressed.2 = state == Pressed,
-                            _ => {}
-                        }
-                    }
-                    MouseWheel {
-                        delta: MouseScrollDelta::LineDelta(_, y),
-                        phase: TouchPhase::Moved,
-                        ..
-                    } |
-                    MouseWheel {
-                        delta: MouseScrollDelta::PixelDelta(_, y),
-                        phase: TouchPhase::Moved,
-                        ..
-                    } => mouse_state.wheel = y,
-                    ReceivedCharacter(c) => imgui.add_input_character(c),
+                    WindowEvent::CloseRequested => quit = true,
                     _ => (),
                 }
             }
         });
 
-        let now = Instant::now();
-        let delta = now - last_frame;
-        let delta_s = delta.as_secs() as f32 + delta.subsec_nanos() as f32 / 1_000_000_000.0;
-        last_frame = now;
-
-        update_mouse(&mut imgui, &mut mouse_state);
-
-        let size_points = window.get_inner_size_points().unwrap();
-        let size_pixels = window.get_inner_size_pixels().unwrap();
+        if platform.hidpi_factor() != hidpi_factor {
+            hidpi_factor = platform.hidpi_factor();
+            configure_fonts(&mut imgui, hidpi_factor);
+            renderer.reload_font_texture(&mut imgui, &mut factory).expect(
+                "Failed to reload fonts",
+            );
+        }
 
-        let ui = imgui.frame(size_points, size_pixels, delta_s);
+        let ui = platform.frame(&mut imgui, window.window());
         if !run_ui(&ui) {
             break;
         }
+        platform.prepare_render(&ui, window.window());
 
         encoder.clear(&main_color, clear_color);
-        renderer.render(ui, &mut factory, &mut encoder).expect(
-            "Rendering failed",
-        );
+        renderer
+            .render(ui, &mut factory, &mut encoder, &mut device)
+            .expect("Rendering failed");
         encoder.flush(&mut device);
         window.context().swap_buffers().unwrap();
         device.cleanup();
@@ -153,45 +94,12 @@ pub fn run<F: FnMut(&Ui) -> bool>(title: String, clear_color: [f32; 4], mut run_
     }
 }
 
-fn configure_keys(imgui: &mut ImGui) {
-    use imgui::ImGuiKey;
-
-    imgui.set_imgui_key(ImGuiKey::Tab, 0);
-    imgui.set_imgui_key(ImGuiKey::LeftArrow, 1);
-    imgui.set_imgui_key(ImGuiKey::RightArrow, 2);
-    imgui.set_imgui_key(ImGuiKey::UpArrow, 3);
-    imgui.set_imgui_key(ImGuiKey::DownArrow, 4);
-    imgui.set_imgui_key(ImGuiKey::PageUp, 5);
-    imgui.set_imgui_key(ImGuiKey::PageDown, 6);
-    imgui.set_imgui_key(ImGuiKey::Home, 7);
-    imgui.set_imgui_key(ImGuiKey::End, 8);
-    imgui.set_imgui_key(ImGuiKey::Delete, 9);
-    imgui.set_imgui_key(ImGuiKey::Backspace, 10);
-    imgui.set_imgui_key(ImGuiKey::Enter, 11);
-    imgui.set_imgui_key(ImGuiKey::Escape, 12);
-    imgui.set_imgui_key(ImGuiKey::A, 13);
-    imgui.set_imgui_key(ImGuiKey::C, 14);
-    imgui.set_imgui_key(ImGuiKey::V, 15);
-    imgui.set_imgui_key(ImGuiKey::X, 16);
-    imgui.set_imgui_key(ImGuiKey::Y, 17);
-    imgui.set_imgui_key(ImGuiKey::Z, 18);
-}
-
-fn update_mouse(imgui: &mut ImGui, mouse_state: &mut MouseState) {
-    let scale = imgui.display_framebuffer_scale();
-    imgui.set_mouse_pos(
-        mouse_state.pos.0 as f32 / scale.0,
-        mouse_state.pos.1 as f32 / scale.1,
-    );
-    imgui.set_mouse_down(
-        &[
-            mouse_state.pressed.0,
-            mouse_state.pressed.1,
-            mouse_state.pressed.2,
-            false,
-            false,
-        ],
-    );
-    imgui.set_mouse_wheel(mouse_state.wheel / scale.1);
-    mouse_state.wheel = 0.0;
+fn configure_fonts(imgui: &mut ImGui, hidpi_factor: f64) {
+    let font_size = (13.0 * hidpi_factor) as f32;
+    {
+        let mut fonts = imgui.fonts();
+        fonts.clear();
+        fonts.add_default_font_with_config(&FontConfig::new().size_pixels(font_size));
+    }
+    imgui.set_font_global_scale((1.0 / hidpi_factor) as f32);
 }
@@ -1,6 +1,7 @@
 extern crate glium;
 extern crate imgui;
 extern crate imgui_glium_renderer;
+extern crate imgui_winit_support;
 
 use imgui::*;
 
@@ -211,6 +212,9 @@ fn show_test_window(ui: &Ui, state: &mut State, opened: &mut bool) {
     if state.show_app_manipulating_window_title {
         show_example_app_manipulating_window_title(ui);
     }
+    if state.show_app_custom_rendering {
+        show_example_app_custom_rendering(ui, &mut state.show_app_custom_rendering);
+    }
     if state.show_app_metrics {
         ui.show_metrics_window(&mut state.show_app_metrics);
     }
@@ -737,3 +741,70 @@ My title is the same as window 1, but my identifier is unique.",
         .position((100.0, 300.0), ImGuiCond::FirstUseEver)
         .build(|| ui.text("This window has a changing title"));
 }
+
+fn show_example_app_custom_rendering(ui: &Ui, opened: &mut bool) {
+    ui.window("Example: Custom rendering")
+        .size((350.0, 560.0), ImGuiCond::FirstUseEver)
+        .opened(opened)
+        .build(|| {
+            ui.text("Primitives");
+            let draw_list = ui.get_window_draw_list();
+            let (x, y) = ui.get_cursor_screen_pos();
+            let col = [1.0, 1.0, 0.4];
+            let sz = 36.0;
+            let spacing = 8.0;
+            let mut x = x + 4.0;
+            let y = y + 4.0;
+            for &filled in &[false, true] {
+                draw_list
+                    .add_circle((x + sz * 0.5, y + sz * 0.5), sz * 0.5, col)
+                    .num_segments(20)
+                    .filled(filled)
+                    .build();
+                x += sz + spacing;
+                draw_list
+                    .add_rect((x, y), (x + sz, y + sz), col)
+                    .filled(filled)
+                    .build();
+                x += sz + spacing;
+                draw_list
+                    .add_rect((x, y), (x + sz, y + sz), col)
+                    .rounding(10.0)
+                    .filled(filled)
+                    .build();
+                x += sz + spacing;
+                draw_list
+                    .add_triangle(
+                        (x + sz * 0.5, y),
+                        (x + sz, y + sz - 0.5),
+                        (x, y + sz - 0.5),
+                        col,
+                    )
+                    .filled(filled)
+                    .build();
+                x += sz + spacing;
+            }
+            draw_list.add_line((x, y), (x + sz, y + sz), col).build();
+            x += sz + spacing;
+            draw_list
+                .add_bezier_curve(
+                    (x, y),
+                    (x + sz * 1.3, y + sz * 0.3),
+                    (x + sz - sz * 1.3, y + sz - sz * 0.3),
+                    (x + sz, y + sz),
+                    col,
+                )
+                .thickness(2.0)
+                .build();
+            x += sz + spacing;
+            draw_list.add_rect_filled_multicolor(
+                (x, y),
+                (x + sz, y + sz),
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            );
+            ui.dummy(((sz + spacing) * 8.0, sz + spacing));
+        });
+}
@@ -2,10 +2,11 @@
 extern crate gfx;
 extern crate imgui;
 
-use gfx::{Bind, Bundle, CommandBuffer, Encoder, Factory, IntoIndexBuffer, Rect, Resources, Slice};
-use gfx::handle::{Buffer, RenderTargetView};
+use gfx::{Bind, Bundle, CommandBuffer, Device, Encoder, Factory, IntoIndexBuffer, Rect, Resources,
+          Slice};
+use gfx::handle::{Buffer, RenderTargetView, Sampler, ShaderResourceView};
 use gfx::traits::FactoryExt;
-use imgui::{DrawList, ImDrawIdx, ImDrawVert, ImGui, Ui};
+use imgui::{DrawCmd, DrawData, DrawList, ImDrawIdx, ImDrawVert, ImGui, TextureId, Textures, Ui};
 
 pub type RendererResult<T> = Result<T, RendererError>;
 
@@ -15,6 +16,7 @@ pub enum RendererError {
     Buffer(gfx::buffer::CreationError),
     Pipeline(gfx::PipelineStateError<String>),
     Combined(gfx::CombinedError),
+    BadTexture(TextureId),
 }
 
 impl From<gfx::UpdateError<usize>> for RendererError {
@@ -80,9 +82,29 @@ impl Shaders {
     }
 }
 
+fn upload_font_texture<R: Resources, F: Factory<R>>(
+    imgui: &mut ImGui,
+    factory: &mut F,
+) -> RendererResult<ShaderResourceView<R, [f32; 4]>> {
+    let (_, texture) = imgui.prepare_texture(|handle| {
+        factory.create_texture_immutable_u8::<gfx::format::Rgba8>(
+            gfx::texture::Kind::D2(
+                handle.width as u16,
+                handle.height as u16,
+                gfx::texture::AaMode::Single,
+            ),
+            &[handle.pixels],
+        )
+    })?;
+    Ok(texture)
+}
+
+pub type Texture<R> = (ShaderResourceView<R, [f32; 4]>, Sampler<R>);
+
 pub struct Renderer<R: Resources> {
     bundle: Bundle<R, pipe::Data<R>>,
     index_buffer: Buffer<R, u16>,
+    textures: Textures<Texture<R>>,
 }
 
 impl<R: Resources> Renderer<R> {
@@ -110,18 +132,10 @@ impl<R: Resources> Renderer<R> {
             gfx::memory::Usage::Dynamic,
             Bind::empty(),
         )?;
-        let (_, texture) = imgui.prepare_texture(|handle| {
-            factory.create_texture_immutable_u8::<gfx::format::Rgba8>(
-                gfx::texture::Kind::D2(
-                    handle.width as u16,
-                    handle.height as u16,
-                    gfx::texture::AaMode::Single,
-                ),
-                &[handle.pixels],
-            )
-        })?;
-        // TODO: set texture id in imgui
+        let font_texture = upload_font_texture(imgui, factory)?;
         let sampler = factory.create_sampler_linear();
+        let mut textures = Textures::new();
+        imgui.set_font_texture_id(textures.insert((font_texture.clone(), sampler.clone())));
         let data = pipe::Data {
             vertex_buffer: vertex_buffer,
             matrix: [
@@ -130,7 +144,7 @@ impl<R: Resources> Renderer<R> {
                 [0.0, 0.0, -1.0, 0.0],
                 [-1.0, 1.0, 0.0, 1.0],
             ],
-            tex: (texture, sampler),
+            tex: (font_texture, sampler),
             out: out,
             scissor: Rect {
                 x: 0,
@@ -149,18 +163,64 @@ impl<R: Resources> Renderer<R> {
         Ok(Renderer {
             bundle: Bundle::new(slice, pso, data),
             index_buffer: index_buffer,
+            textures: textures,
         })
     }
+    /// Rebuilds the font atlas texture and uploads it again.
+    ///
+    /// This needs to be called after fonts have been changed, e.g. when the atlas has been
+    /// rebuilt for a different hidpi factor.
+    pub fn reload_font_texture<F: Factory<R>>(
+        &mut self,
+        imgui: &mut ImGui,
+        factory: &mut F,
+    ) -> RendererResult<()> {
+        let texture = upload_font_texture(imgui, factory)?;
+        let sampler = factory.create_sampler_linear();
+        self.textures.replace(imgui.font_texture_id(), (texture, sampler));
+        Ok(())
+    }
+    /// Returns the texture registry, which can be used to register application textures for
+    /// drawing with imgui.
+    pub fn textures(&mut self) -> &mut Textures<Texture<R>> { &mut self.textures }
     pub fn update_render_target(&mut self, out: RenderTargetView<R, gfx::format::Rgba8>) {
         self.bundle.data.out = out;
     }
-    pub fn render<'a, F: Factory<R>, C: CommandBuffer<R>>(
+    /// Renders a frame.
+    ///
+    /// The encoder is flushed to the device before every draw callback, so that anything the
+    /// callback draws is ordered correctly with the imgui geometry around it.
+    pub fn render<'a, F, C, D>(
         &mut self,
         ui: Ui<'a>,
         factory: &mut F,
         encoder: &mut Encoder<R, C>,
-    ) -> RendererResult<()> {
-        let (width, height) = ui.imgui().display_size();
+        device: &mut D,
+    ) -> RendererResult<()>
+    where
+        F: Factory<R>,
+        C: CommandBuffer<R>,
+        D: Device<Resources = R, CommandBuffer = C>,
+    {
+        let draw_data = ui.render();
+        self.render_draw_data(factory, encoder, device, &draw_data)
+    }
+    /// Renders draw data that isn't necessarily rendered by a `Ui` of this program, such as
+    /// `OwnedDrawData` or a loaded `FrameCapture`. The display size of the draw data is scaled
+    /// to the whole render target.
+    pub fn render_draw_data<F, C, D>(
+        &mut self,
+        factory: &mut F,
+        encoder: &mut Encoder<R, C>,
+        device: &mut D,
+        draw_data: &DrawData,
+    ) -> RendererResult<()>
+    where
+        F: Factory<R>,
+        C: CommandBuffer<R>,
+        D: Device<Resources = R, CommandBuffer = C>,
+    {
+        let (width, height) = draw_data.display_size();
 
         if width == 0.0 || height == 0.0 {
             return Ok(());
@@ -172,22 +232,47 @@ impl<R: Resources> Renderer<R> {
             [-1.0, 1.0, 0.0, 1.0],
         ];
 
-        ui.render(|ui, draw_list| {
-            self.render_draw_list(ui, factory, encoder, &draw_list)
-        })
+        let (target_width, target_height, _, _) = self.bundle.data.out.get_dimensions();
+        let scale = (target_width as f32 / width, target_height as f32 / height);
+        for draw_list in draw_data {
+            self.render_draw_list(factory, encoder, device, &draw_list, scale)?;
+        }
+        Ok(())
     }
-    fn render_draw_list<'a, F: Factory<R>, C: CommandBuffer<R>>(
+    fn render_draw_list<F, C, D>(
         &mut self,
-        ui: &'a Ui<'a>,
         factory: &mut F,
         encoder: &mut Encoder<R, C>,
-        draw_list: &DrawList<'a>,
-    ) -> RendererResult<()> {
-        let (scale_width, scale_height) = ui.imgui().display_framebuffer_scale();
-
+        device: &mut D,
+        draw_list: &DrawList,
+        (scale_width, scale_height): (f32, f32),
+    ) -> RendererResult<()>
+    where
+        F: Factory<R>,
+        C: CommandBuffer<R>,
+        D: Device<Resources = R, CommandBuffer = C>,
+    {
         self.bundle.slice.start = 0;
-        for cmd in draw_list.cmd_buffer {
-            // TODO: check cmd.texture_id
+        for cmd in draw_list.commands() {
+            let (count, clip_rect, texture_id) = match cmd {
+                DrawCmd::Elements {
+                    count,
+                    clip_rect,
+                    texture_id,
+                } => (count, clip_rect, texture_id),
+                // The complete pipeline state is encoded with every draw call
+                DrawCmd::ResetRenderState => continue,
+                // The draw calls encoded so far need to reach the device before the callback
+                DrawCmd::Callback(callback) => {
+                    encoder.flush(device);
+                    callback.invoke();
+                    continue;
+                }
+            };
+            self.bundle.data.tex = self.textures
+                .get(texture_id)
+                .ok_or(RendererError::BadTexture(texture_id))?
+                .clone();
 
             self.upload_vertex_buffer(
                 factory,
@@ -200,12 +285,12 @@ impl<R: Resources> Renderer<R> {
                 draw_list.idx_buffer,
             )?;
 
-            self.bundle.slice.end = self.bundle.slice.start + cmd.elem_count;
+            self.bundle.slice.end = self.bundle.slice.start + count as u32;
             self.bundle.data.scissor = Rect {
-                x: (cmd.clip_rect.x * scale_width) as u16,
-                y: (cmd.clip_rect.y * scale_height) as u16,
-                w: ((cmd.clip_rect.z - cmd.clip_rect.x).abs() * scale_width) as u16,
-                h: ((cmd.clip_rect.w - cmd.clip_rect.y).abs() * scale_height) as u16,
+                x: (clip_rect[0] * scale_width) as u16,
+                y: (clip_rect[1] * scale_height) as u16,
+                w: ((clip_rect[2] - clip_rect[0]).abs() * scale_width) as u16,
+                h: ((clip_rect[3] - clip_rect[1]).abs() * scale_height) as u16,
             };
             self.bundle.encode(encoder);
             self.bundle.slice.start = self.bundle.slice.end;
@@ -2,13 +2,13 @@
 extern crate glium;
 extern crate imgui;
 
-use glium::{DrawError, GlObject, IndexBuffer, Program, Surface, Texture2d, VertexBuffer};
+use glium::{DrawError, IndexBuffer, Program, Surface, Texture2d, VertexBuffer};
 use glium::backend::{Context, Facade};
 use glium::index::{self, PrimitiveType};
 use glium::program;
 use glium::texture;
 use glium::vertex;
-use imgui::{DrawList, ImDrawIdx, ImDrawVert, ImGui, Ui};
+use imgui::{DrawCmd, DrawData, DrawList, ImDrawIdx, ImDrawVert, ImGui, TextureId, Textures, Ui};
 use std::borrow::Cow;
 use std::fmt;
 use std::rc::Rc;
@@ -22,6 +22,7 @@ pub enum RendererError {
     Program(program::ProgramChooserCreationError),
     Texture(texture::TextureCreationError),
     Draw(DrawError),
+    BadTexture(TextureId),
 }
 
 impl fmt::Display for RendererError {
@@ -33,6 +34,7 @@ impl fmt::Display for RendererError {
             Program(ref e) => write!(f, "Program creation failed: {}", e),
             Texture(_) => write!(f, "Texture creation failed"),
             Draw(ref e) => write!(f, "Drawing failed: {}", e),
+            BadTexture(ref t) => write!(f, "Bad texture ID: {}", t.id()),
         }
     }
 }
@@ -71,20 +73,65 @@ impl Renderer {
         })
     }
 
+    /// Rebuilds the font atlas texture and uploads it again.
+    ///
+    /// This needs to be called after fonts have been changed, e.g. when the atlas has been
+    /// rebuilt for a different hidpi factor.
+    pub fn reload_font_texture(&mut self, imgui: &mut ImGui) -> RendererResult<()> {
+        let texture = upload_font_texture(imgui, &self.ctx)?;
+        let id = imgui.font_texture_id();
+        self.device_objects.textures.replace(id, texture);
+        Ok(())
+    }
+
+    /// Returns the texture registry, which can be used to register application textures for
+    /// drawing with imgui.
+    pub fn textures(&mut self) -> &mut Textures<Texture2d> { &mut self.device_objects.textures }
+
     pub fn render<'a, S: Surface>(&mut self, surface: &mut S, ui: Ui<'a>) -> RendererResult<()> {
+        let draw_data = ui.render();
+        self.render_draw_data(surface, &draw_data)
+    }
+
+    /// Renders draw data that isn't necessarily rendered by a `Ui` of this program, such as
+    /// `OwnedDrawData` or a loaded `FrameCapture`. The display size of the draw data is scaled
+    /// to the whole surface.
+    pub fn render_draw_data<S: Surface>(
+        &mut self,
+        surface: &mut S,
+        draw_data: &DrawData,
+    ) -> RendererResult<()> {
+        let display_size = draw_data.display_size();
+        if display_size.0 == 0.0 || display_size.1 == 0.0 {
+            return Ok(());
+        }
+        let (width, height) = surface.get_dimensions();
+        let scale = (width as f32 / display_size.0, height as f32 / display_size.1);
         let _ = self.ctx.insert_debug_marker("imgui-rs: starting rendering");
-        let result = ui.render(|ui, draw_list| {
-            self.render_draw_list(surface, ui, &draw_list)
-        });
+        let result = self.render_draw_lists(surface, draw_data, display_size, scale);
         let _ = self.ctx.insert_debug_marker("imgui-rs: rendering finished");
         result
     }
 
-    fn render_draw_list<'a, S: Surface>(
+    fn render_draw_lists<S: Surface>(
+        &mut self,
+        surface: &mut S,
+        draw_data: &DrawData,
+        display_size: (f32, f32),
+        scale: (f32, f32),
+    ) -> RendererResult<()> {
+        for draw_list in draw_data {
+            self.render_draw_list(surface, &draw_list, display_size, scale)?;
+        }
+        Ok(())
+    }
+
+    fn render_draw_list<S: Surface>(
         &mut self,
         surface: &mut S,
-        ui: &'a Ui<'a>,
-        draw_list: &DrawList<'a>,
+        draw_list: &DrawList,
+        (width, height): (f32, f32),
+        (scale_width, scale_height): (f32, f32),
     ) -> RendererResult<()> {
         use glium::{Blend, DrawParameters, Rect};
         use glium::uniforms::{MinifySamplerFilter, MagnifySamplerFilter};
@@ -98,27 +145,32 @@ impl Renderer {
             draw_list.idx_buffer,
         )?;
 
-        let (width, height) = ui.imgui().display_size();
-        let (scale_width, scale_height) = ui.imgui().display_framebuffer_scale();
-
-        if width == 0.0 || height == 0.0 {
-            return Ok(());
-        }
-
         let matrix = [
             [2.0 / width as f32, 0.0, 0.0, 0.0],
             [0.0, 2.0 / -(height as f32), 0.0, 0.0],
             [0.0, 0.0, -1.0, 0.0],
             [-1.0, 1.0, 0.0, 1.0],
         ];
-        let font_texture_id = self.device_objects.texture.get_id() as usize;
-
         let mut idx_start = 0;
-        for cmd in draw_list.cmd_buffer {
-            // We don't support custom textures...yet!
-            assert!(cmd.texture_id as usize == font_texture_id);
+        for cmd in draw_list.commands() {
+            let (count, clip_rect, texture_id) = match cmd {
+                DrawCmd::Elements {
+                    count,
+                    clip_rect,
+                    texture_id,
+                } => (count, clip_rect, texture_id),
+                // glium sets the complete render state for every draw call
+                DrawCmd::ResetRenderState => continue,
+                DrawCmd::Callback(callback) => {
+                    callback.invoke();
+                    continue;
+                }
+            };
+            let texture = self.device_objects.textures.get(texture_id).ok_or(
+                RendererError::BadTexture(texture_id),
+            )?;
 
-            let idx_end = idx_start + cmd.elem_count as usize;
+            let idx_end = idx_start + count;
 
             surface.draw(
                 &self.device_objects.vertex_buffer,
@@ -129,17 +181,17 @@ impl Renderer {
                 &self.device_objects.program,
                 &uniform! {
                       matrix: matrix,
-                      tex: self.device_objects.texture.sampled()
+                      tex: texture.sampled()
                           .magnify_filter(MagnifySamplerFilter::Nearest)
                           .minify_filter(MinifySamplerFilter::Nearest),
                   },
                 &DrawParameters {
                     blend: Blend::alpha_blending(),
                     scissor: Some(Rect {
-                        left: (cmd.clip_rect.x * scale_width) as u32,
-                        bottom: ((height - cmd.clip_rect.w) * scale_height) as u32,
-                        width: ((cmd.clip_rect.z - cmd.clip_rect.x) * scale_width) as u32,
-                        height: ((cmd.clip_rect.w - cmd.clip_rect.y) * scale_height) as u32,
+                        left: (clip_rect[0] * scale_width) as u32,
+                        bottom: ((height - clip_rect[3]) * scale_height) as u32,
+                        width: ((clip_rect[2] - clip_rect[0]) * scale_width) as u32,
+                        height: ((clip_rect[3] - clip_rect[1]) * scale_height) as u32,
                     }),
                     ..DrawParameters::default()
                 },
@@ -156,7 +208,7 @@ pub struct DeviceObjects {
     vertex_buffer: VertexBuffer<ImDrawVert>,
     index_buffer: IndexBuffer<ImDrawIdx>,
     program: Program,
-    texture: Texture2d,
+    textures: Textures<Texture2d>,
 }
 
 fn compile_default_program<F: Facade>(
@@ -192,10 +244,23 @@ fn compile_default_program<F: Facade>(
     )
 }
 
+fn upload_font_texture<F: Facade>(im_gui: &mut ImGui, ctx: &F) -> RendererResult<Texture2d> {
+    use glium::texture::{ClientFormat, RawImage2d};
+
+    let texture = im_gui.prepare_texture(|handle| {
+        let data = RawImage2d {
+            data: Cow::Borrowed(handle.pixels),
+            width: handle.width,
+            height: handle.height,
+            format: ClientFormat::U8U8U8U8,
+        };
+        Texture2d::new(ctx, data)
+    })?;
+    Ok(texture)
+}
+
 impl DeviceObjects {
     pub fn init<F: Facade>(im_gui: &mut ImGui, ctx: &F) -> RendererResult<DeviceObjects> {
-        use glium::texture::{ClientFormat, RawImage2d};
-
         let vertex_buffer = VertexBuffer::empty_dynamic(ctx, 0)?;
         let index_buffer = IndexBuffer::empty_dynamic(
             ctx,
@@ -204,22 +269,15 @@ impl DeviceObjects {
         )?;
 
         let program = compile_default_program(ctx)?;
-        let texture = im_gui.prepare_texture(|handle| {
-            let data = RawImage2d {
-                data: Cow::Borrowed(handle.pixels),
-                width: handle.width,
-                height: handle.height,
-                format: ClientFormat::U8U8U8U8,
-            };
-            Texture2d::new(ctx, data)
-        })?;
-        im_gui.set_texture_id(texture.get_id() as usize);
+        let mut textures = Textures::new();
+        let font_texture = upload_font_texture(im_gui, ctx)?;
+        im_gui.set_font_texture_id(textures.insert(font_texture));
 
         Ok(DeviceObjects {
             vertex_buffer: vertex_buffer,
             index_buffer: index_buffer,
             program: program,
-            texture: texture,
+            textures: textures,
         })
     }
     pub fn upload_vertex_buffer<F: Facade>(
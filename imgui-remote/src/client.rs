@@ -0,0 +1,153 @@
+use imgui::{sys, CapturedTexture, InputEvent, OwnedDrawData, OwnedDrawList};
+use std::io::{self, Cursor, ErrorKind, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::rc::Rc;
+
+use protocol::{self, invalid_data, read_f32, read_u32, read_u8, truncated, MessageReader,
+               LIST_FULL, LIST_UNCHANGED, MSG_FONT_TEXTURE, MSG_FRAME, MSG_INPUT};
+
+/// What a `RemoteClient` has received since the last call to `receive`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Received {
+    /// A new frame is available through `RemoteClient::frame_mut`
+    pub frame: bool,
+    /// A new font texture is available through `RemoteClient::font_texture`, and needs to be
+    /// uploaded to the renderer
+    pub font_texture: bool,
+}
+
+/// The viewer side of a remote UI.
+///
+/// It receives the frames of a `RemoteServer` as `OwnedDrawData`, which can be drawn with any
+/// renderer, and sends input events back to the server.
+pub struct RemoteClient {
+    stream: TcpStream,
+    reader: MessageReader,
+    font_texture: Option<CapturedTexture>,
+    // The draw lists as received, which unchanged draw lists of the next frame refer to
+    lists: Vec<Rc<OwnedDrawList>>,
+    frame: Option<OwnedDrawData>,
+}
+
+impl RemoteClient {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<RemoteClient> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        Ok(RemoteClient {
+            stream: stream,
+            reader: MessageReader::default(),
+            font_texture: None,
+            lists: Vec::new(),
+            frame: None,
+        })
+    }
+
+    /// Returns the font atlas texture of the server, once it has been received.
+    pub fn font_texture(&self) -> Option<&CapturedTexture> { self.font_texture.as_ref() }
+    /// Returns the latest frame, once one has been received.
+    pub fn frame(&self) -> Option<&OwnedDrawData> { self.frame.as_ref() }
+    /// Returns the latest frame for rendering with `OwnedDrawData::draw_data`. Changes to it,
+    /// e.g. by `DrawData::scale_clip_rects`, don't affect the frames received later.
+    pub fn frame_mut(&mut self) -> Option<&mut OwnedDrawData> { self.frame.as_mut() }
+
+    /// Sends an input event, which the server applies to its UI.
+    pub fn send_event(&mut self, event: InputEvent) -> io::Result<()> {
+        let payload = protocol::encode_event(&event);
+        protocol::write_message(&mut self.stream, MSG_INPUT, &payload)
+    }
+
+    /// Processes the messages that have arrived without waiting for more.
+    pub fn receive(&mut self) -> io::Result<Received> {
+        self.stream.set_nonblocking(true)?;
+        let open = self.reader.fill_nonblocking(&mut self.stream);
+        self.stream.set_nonblocking(false)?;
+        let open = open?;
+        let received = self.process_messages()?;
+        if !open {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        Ok(received)
+    }
+
+    /// Waits until a new frame has arrived.
+    pub fn wait_for_frame(&mut self) -> io::Result<Received> {
+        let mut received = self.process_messages()?;
+        while !received.frame {
+            if !self.reader.fill_blocking(&mut self.stream)? {
+                return Err(ErrorKind::UnexpectedEof.into());
+            }
+            let more = self.process_messages()?;
+            received.frame = more.frame;
+            received.font_texture |= more.font_texture;
+        }
+        Ok(received)
+    }
+
+    fn process_messages(&mut self) -> io::Result<Received> {
+        let mut received = Received::default();
+        while let Some((kind, payload)) = self.reader.next_message()? {
+            match kind {
+                MSG_FONT_TEXTURE => {
+                    let texture = CapturedTexture::read_from(&mut Cursor::new(payload))?;
+                    self.font_texture = Some(texture);
+                    received.font_texture = true;
+                }
+                MSG_FRAME => {
+                    // The previous frame is kept if the message is invalid
+                    let (frame, lists) = read_frame(&mut Cursor::new(payload), &self.lists)
+                        .map_err(|e| truncated(e, "Truncated frame"))?;
+                    self.lists = lists;
+                    self.frame = Some(frame);
+                    received.frame = true;
+                }
+                _ => return Err(invalid_data("Unexpected message from server")),
+            }
+        }
+        Ok(received)
+    }
+}
+
+/// Reads a frame, and returns it together with the draw lists as received.
+fn read_frame<R: Read>(
+    r: &mut R,
+    previous: &[Rc<OwnedDrawList>],
+) -> io::Result<(OwnedDrawData, Vec<Rc<OwnedDrawList>>)> {
+    let display_size = (read_f32(r)?, read_f32(r)?);
+    let count = read_u32(r)?;
+    let mut lists = Vec::new();
+    for i in 0..count as usize {
+        let list = match read_u8(r)? {
+            LIST_UNCHANGED => {
+                previous
+                    .get(i)
+                    .cloned()
+                    .ok_or_else(|| invalid_data("Unchanged draw list doesn't exist"))?
+            }
+            LIST_FULL => Rc::new(OwnedDrawList::read_from(r)?),
+            _ => return Err(invalid_data("Invalid draw list")),
+        };
+        lists.push(list);
+    }
+    let mut frame = OwnedDrawData::new(display_size);
+    frame.draw_lists = lists.iter().map(|list| copy_draw_list(list)).collect();
+    Ok((frame, lists))
+}
+
+fn copy_draw_list(list: &OwnedDrawList) -> OwnedDrawList {
+    OwnedDrawList {
+        cmd_buffer: list.cmd_buffer
+            .iter()
+            .map(|cmd| {
+                sys::ImDrawCmd {
+                    elem_count: cmd.elem_count,
+                    clip_rect: cmd.clip_rect,
+                    texture_id: cmd.texture_id,
+                    user_callback: cmd.user_callback,
+                    user_callback_data: cmd.user_callback_data,
+                }
+            })
+            .collect(),
+        idx_buffer: list.idx_buffer.clone(),
+        vtx_buffer: list.vtx_buffer.clone(),
+    }
+}
@@ -0,0 +1,126 @@
+//! Remote UI for [imgui](https://crates.io/crates/imgui) over TCP.
+//!
+//! An application without a window, such as a game server, creates a `RemoteServer` and sends
+//! it every rendered frame. Viewers connect with a `RemoteClient`, draw the received frames with
+//! any renderer and send their mouse and keyboard input back, which the server queues as
+//! `InputEvent`s of the application's `ImGui` context.
+//!
+//! Draw lists and the font atlas are encoded like in an `imgui::FrameCapture`. The font atlas is
+//! sent once per viewer, and draw lists that haven't changed since the previous frame are sent
+//! as one-byte references.
+//!
+//! ```rust,no_run
+//! # extern crate imgui;
+//! # extern crate imgui_remote;
+//! # use imgui::ImGui;
+//! # use imgui_remote::RemoteServer;
+//! # fn main() {
+//! let mut imgui = ImGui::init();
+//! let mut server = RemoteServer::bind("127.0.0.1:7000", &mut imgui).unwrap();
+//!
+//! loop {
+//!     server.accept_clients().unwrap();
+//!     server.receive_input(&mut imgui);
+//!
+//!     let ui = imgui.frame((1280, 720), (1280, 720), 1.0 / 60.0);
+//!     ui.window("Server status").build(|| ui.text("Running"));
+//!     server.render(ui);
+//! #   break;
+//! }
+//! # }
+//! ```
+extern crate imgui;
+
+pub use client::{Received, RemoteClient};
+pub use server::RemoteServer;
+
+mod client;
+mod protocol;
+mod server;
+
+#[cfg(test)]
+mod tests {
+    use imgui::{ImGui, ImGuiCond, InputEvent, MouseButton};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    fn frame(imgui: &mut ImGui, server: &mut RemoteServer, clicks: &mut u32) {
+        let ui = imgui.frame((640, 480), (640, 480), 1.0 / 60.0);
+        ui.window("Remote")
+            .position((0.0, 0.0), ImGuiCond::Always)
+            .size((200.0, 100.0), ImGuiCond::Always)
+            .build(|| if ui.button("Click me") {
+                *clicks += 1;
+            });
+        server.render(ui);
+    }
+
+    fn receive_frame(
+        imgui: &mut ImGui,
+        server: &mut RemoteServer,
+        client: &mut RemoteClient,
+    ) -> Received {
+        let mut received = Received::default();
+        while !received.frame {
+            // Sends whatever didn't fit in the socket buffers yet
+            server.receive_input(imgui);
+            let more = client.receive().unwrap();
+            received.frame = more.frame;
+            received.font_texture |= more.font_texture;
+            thread::sleep(Duration::from_millis(1));
+        }
+        received
+    }
+
+    #[test]
+    fn loopback() {
+        let mut imgui = ImGui::init();
+        imgui.set_ini_filename(None);
+        let mut server = RemoteServer::bind("127.0.0.1:0", &mut imgui).unwrap();
+        let mut client = RemoteClient::connect(server.local_addr().unwrap()).unwrap();
+        while server.client_count() == 0 {
+            server.accept_clients().unwrap();
+            thread::sleep(Duration::from_millis(1));
+        }
+        let mut clicks = 0;
+
+        frame(&mut imgui, &mut server, &mut clicks);
+        let received = receive_frame(&mut imgui, &mut server, &mut client);
+        assert!(received.frame && received.font_texture);
+        assert!(!client.font_texture().unwrap().pixels.is_empty());
+        assert_eq!(client.frame().unwrap().display_size, (640.0, 480.0));
+        let first_frame_bytes = server.bytes_sent();
+
+        // The window may be hidden in its first frame, so the UI is only static afterwards
+        frame(&mut imgui, &mut server, &mut clicks);
+        let received = receive_frame(&mut imgui, &mut server, &mut client);
+        assert!(received.frame && !received.font_texture);
+        let bytes_sent = server.bytes_sent();
+        assert!(bytes_sent > first_frame_bytes);
+
+        // An identical frame only references the draw lists of the previous one
+        frame(&mut imgui, &mut server, &mut clicks);
+        receive_frame(&mut imgui, &mut server, &mut client);
+        assert!(server.bytes_sent() - bytes_sent < 64);
+        assert!(client.frame_mut().unwrap().draw_data().total_vtx_count() > 0);
+
+        let events = [
+            InputEvent::MousePos(20.0, 30.0),
+            InputEvent::MouseButton(MouseButton::Left, true),
+            InputEvent::MouseButton(MouseButton::Left, false),
+        ];
+        for &event in events.iter() {
+            client.send_event(event).unwrap();
+        }
+        while imgui.queued_event_count() < events.len() {
+            server.receive_input(&mut imgui);
+            thread::sleep(Duration::from_millis(1));
+        }
+        for _ in 0..4 {
+            frame(&mut imgui, &mut server, &mut clicks);
+        }
+        assert_eq!(clicks, 1);
+    }
+}
@@ -0,0 +1,212 @@
+//! The wire protocol shared by the server and the client.
+//!
+//! Every message is a kind byte and a little-endian `u32` payload length, followed by the
+//! payload. Draw lists and the font texture use the `FrameCapture` encoding of imgui.
+use imgui::{InputEvent, Key, MouseButton};
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Server to client: the font atlas texture as a `CapturedTexture`
+pub const MSG_FONT_TEXTURE: u8 = 1;
+/// Server to client: the display size and the draw lists of a frame
+pub const MSG_FRAME: u8 = 2;
+/// Client to server: an input event
+pub const MSG_INPUT: u8 = 3;
+
+/// A draw list that is identical to the one at the same index in the previous frame
+pub const LIST_UNCHANGED: u8 = 0;
+/// A draw list that follows in full
+pub const LIST_FULL: u8 = 1;
+
+const HEADER_SIZE: usize = 5;
+const MAX_PAYLOAD_SIZE: usize = 256 * 1024 * 1024;
+
+pub fn invalid_data(msg: &str) -> io::Error { io::Error::new(ErrorKind::InvalidData, msg) }
+
+/// Replaces an `UnexpectedEof` error, i.e. a payload that ended in the middle of a value, with
+/// an `InvalidData` error with the given message.
+pub fn truncated(error: io::Error, msg: &str) -> io::Error {
+    if error.kind() == ErrorKind::UnexpectedEof {
+        invalid_data(msg)
+    } else {
+        error
+    }
+}
+
+pub fn write_message<W: Write>(w: &mut W, kind: u8, payload: &[u8]) -> io::Result<()> {
+    write_u8(w, kind)?;
+    write_u32(w, payload.len() as u32)?;
+    w.write_all(payload)?;
+    w.flush()
+}
+
+/// Collects received bytes and splits them into messages.
+#[derive(Default)]
+pub struct MessageReader {
+    buf: Vec<u8>,
+}
+
+impl MessageReader {
+    /// Reads everything that is available from a non-blocking stream. Returns false if the
+    /// stream has been closed.
+    pub fn fill_nonblocking<R: Read>(&mut self, r: &mut R) -> io::Result<bool> {
+        let mut chunk = [0; 4096];
+        loop {
+            match r.read(&mut chunk) {
+                Ok(0) => return Ok(false),
+                Ok(n) => self.buf.extend_from_slice(&chunk[..n]),
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => return Ok(true),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    /// Blocks until some bytes have been read. Returns false if the stream has been closed.
+    pub fn fill_blocking<R: Read>(&mut self, r: &mut R) -> io::Result<bool> {
+        let mut chunk = [0; 4096];
+        loop {
+            match r.read(&mut chunk) {
+                Ok(0) => return Ok(false),
+                Ok(n) => {
+                    self.buf.extend_from_slice(&chunk[..n]);
+                    return Ok(true);
+                }
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    /// Removes the next complete message from the buffer.
+    pub fn next_message(&mut self) -> io::Result<Option<(u8, Vec<u8>)>> {
+        if self.buf.len() < HEADER_SIZE {
+            return Ok(None);
+        }
+        let len = read_u32(&mut &self.buf[1..HEADER_SIZE])? as usize;
+        if len > MAX_PAYLOAD_SIZE {
+            return Err(invalid_data("Message is too large"));
+        }
+        if self.buf.len() < HEADER_SIZE + len {
+            return Ok(None);
+        }
+        let kind = self.buf[0];
+        let payload = self.buf[HEADER_SIZE..HEADER_SIZE + len].to_vec();
+        self.buf.drain(..HEADER_SIZE + len);
+        Ok(Some((kind, payload)))
+    }
+}
+
+const EVENT_MOUSE_POS: u8 = 0;
+const EVENT_MOUSE_BUTTON: u8 = 1;
+const EVENT_MOUSE_WHEEL: u8 = 2;
+const EVENT_KEY: u8 = 3;
+const EVENT_CHAR: u8 = 4;
+const EVENT_FOCUS: u8 = 5;
+
+const MOUSE_BUTTONS: [MouseButton; 5] = [
+    MouseButton::Left,
+    MouseButton::Right,
+    MouseButton::Middle,
+    MouseButton::Extra1,
+    MouseButton::Extra2,
+];
+
+pub fn encode_event(event: &InputEvent) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(9);
+    // Writing to a Vec can't fail
+    write_event(&mut buf, event).unwrap();
+    buf
+}
+
+fn write_event<W: Write>(w: &mut W, event: &InputEvent) -> io::Result<()> {
+    match *event {
+        InputEvent::MousePos(x, y) => {
+            write_u8(w, EVENT_MOUSE_POS)?;
+            write_f32(w, x)?;
+            write_f32(w, y)
+        }
+        InputEvent::MouseButton(button, pressed) => {
+            w.write_all(&[EVENT_MOUSE_BUTTON, button.index() as u8, pressed as u8])
+        }
+        InputEvent::MouseWheel(delta) => {
+            write_u8(w, EVENT_MOUSE_WHEEL)?;
+            write_f32(w, delta)
+        }
+        InputEvent::Key(key, pressed) => {
+            w.write_all(&[EVENT_KEY, key.index() as u8, pressed as u8])
+        }
+        InputEvent::Char(c) => {
+            write_u8(w, EVENT_CHAR)?;
+            write_u32(w, c as u32)
+        }
+        InputEvent::Focus(focused) => w.write_all(&[EVENT_FOCUS, focused as u8]),
+    }
+}
+
+pub fn decode_event(buf: &[u8]) -> io::Result<InputEvent> {
+    read_event(&mut &buf[..]).map_err(|e| truncated(e, "Truncated input event"))
+}
+
+fn read_event<R: Read>(r: &mut R) -> io::Result<InputEvent> {
+    let event = match read_u8(r)? {
+        EVENT_MOUSE_POS => InputEvent::MousePos(read_f32(r)?, read_f32(r)?),
+        EVENT_MOUSE_BUTTON => {
+            let button = MOUSE_BUTTONS
+                .get(read_u8(r)? as usize)
+                .cloned()
+                .ok_or_else(|| invalid_data("Invalid mouse button"))?;
+            InputEvent::MouseButton(button, read_bool(r)?)
+        }
+        EVENT_MOUSE_WHEEL => InputEvent::MouseWheel(read_f32(r)?),
+        EVENT_KEY => {
+            let key = Key::from_index(read_u8(r)? as usize)
+                .ok_or_else(|| invalid_data("Invalid key"))?;
+            InputEvent::Key(key, read_bool(r)?)
+        }
+        EVENT_CHAR => {
+            let c = ::std::char::from_u32(read_u32(r)?)
+                .ok_or_else(|| invalid_data("Invalid character"))?;
+            InputEvent::Char(c)
+        }
+        EVENT_FOCUS => InputEvent::Focus(read_bool(r)?),
+        _ => return Err(invalid_data("Invalid input event")),
+    };
+    Ok(event)
+}
+
+pub fn write_u8<W: Write>(w: &mut W, value: u8) -> io::Result<()> { w.write_all(&[value]) }
+
+pub fn write_u32<W: Write>(w: &mut W, value: u32) -> io::Result<()> {
+    w.write_all(&[
+        value as u8,
+        (value >> 8) as u8,
+        (value >> 16) as u8,
+        (value >> 24) as u8,
+    ])
+}
+
+pub fn write_f32<W: Write>(w: &mut W, value: f32) -> io::Result<()> {
+    write_u32(w, value.to_bits())
+}
+
+pub fn read_u8<R: Read>(r: &mut R) -> io::Result<u8> {
+    let mut buf = [0];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+pub fn read_bool<R: Read>(r: &mut R) -> io::Result<bool> {
+    match read_u8(r)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(invalid_data("Invalid boolean")),
+    }
+}
+
+pub fn read_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    r.read_exact(&mut buf)?;
+    Ok(
+        buf[0] as u32 | (buf[1] as u32) << 8 | (buf[2] as u32) << 16 | (buf[3] as u32) << 24,
+    )
+}
+
+pub fn read_f32<R: Read>(r: &mut R) -> io::Result<f32> { read_u32(r).map(f32::from_bits) }
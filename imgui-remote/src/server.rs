@@ -0,0 +1,304 @@
+use imgui::{CapturedTexture, DrawData, ImGui, OwnedDrawData, Ui};
+use std::io::{self, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::rc::Rc;
+
+use protocol::{self, invalid_data, write_f32, write_u32, MessageReader, LIST_FULL,
+               LIST_UNCHANGED, MSG_FONT_TEXTURE, MSG_FRAME, MSG_INPUT};
+
+/// The application side of a remote UI.
+///
+/// It accepts viewer connections, sends every rendered frame to all of them and applies their
+/// input to the `ImGui` context through its input event queue. The server doesn't wait for
+/// viewers to connect or send input, so it can be driven from the frame loop of a headless
+/// application.
+///
+/// Sending never blocks either: each viewer has an outgoing buffer, and a viewer that hasn't
+/// received the previous frame yet skips new frames until it has caught up.
+pub struct RemoteServer {
+    listener: TcpListener,
+    clients: Vec<Connection>,
+    font_texture: Vec<u8>,
+    bytes_sent: u64,
+    frames_skipped: u64,
+}
+
+struct Connection {
+    stream: TcpStream,
+    reader: MessageReader,
+    font_texture_sent: bool,
+    // The encoded draw lists of the last frame queued for this client
+    last_lists: Vec<Rc<Vec<u8>>>,
+    // Queued bytes that the socket hasn't accepted yet
+    outgoing: Vec<u8>,
+}
+
+impl RemoteServer {
+    /// Starts listening for viewers on the given address, e.g. `"127.0.0.1:7000"`.
+    pub fn bind<A: ToSocketAddrs>(addr: A, imgui: &mut ImGui) -> io::Result<RemoteServer> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(RemoteServer {
+            listener: listener,
+            clients: Vec::new(),
+            font_texture: encode_font_texture(imgui)?,
+            bytes_sent: 0,
+            frames_skipped: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> { self.listener.local_addr() }
+    /// Returns the number of connected viewers.
+    pub fn client_count(&self) -> usize { self.clients.len() }
+    /// Returns the total number of bytes sent to viewers, including message headers.
+    pub fn bytes_sent(&self) -> u64 { self.bytes_sent }
+    /// Returns the total number of frames that viewers skipped because they hadn't received the
+    /// previous frame yet.
+    pub fn frames_skipped(&self) -> u64 { self.frames_skipped }
+
+    /// Sends the font atlas texture to all viewers again.
+    ///
+    /// This needs to be called after fonts have been changed.
+    pub fn reload_font_texture(&mut self, imgui: &mut ImGui) -> io::Result<()> {
+        self.font_texture = encode_font_texture(imgui)?;
+        for client in &mut self.clients {
+            client.font_texture_sent = false;
+        }
+        Ok(())
+    }
+
+    /// Accepts pending viewer connections.
+    pub fn accept_clients(&mut self) -> io::Result<()> {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nodelay(true)?;
+                    stream.set_nonblocking(true)?;
+                    self.clients.push(Connection {
+                        stream: stream,
+                        reader: MessageReader::default(),
+                        font_texture_sent: false,
+                        last_lists: Vec::new(),
+                        outgoing: Vec::new(),
+                    });
+                }
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Queues the input received from viewers with `ImGui::push_event`, so it's applied when
+    /// the next frame starts. Viewers that have disconnected or sent invalid data are dropped.
+    ///
+    /// This also continues sending the frames that viewers haven't received completely.
+    pub fn receive_input(&mut self, imgui: &mut ImGui) {
+        let mut i = 0;
+        while i < self.clients.len() {
+            let result = {
+                let client = &mut self.clients[i];
+                receive_client_input(client, imgui).and_then(|_| client.flush())
+            };
+            match result {
+                Ok(bytes) => {
+                    self.bytes_sent += bytes as u64;
+                    i += 1;
+                }
+                Err(_) => {
+                    self.clients.remove(i);
+                }
+            }
+        }
+    }
+
+    /// Renders the frame and sends it to all viewers, like the `render` method of a renderer.
+    pub fn render<'a>(&mut self, ui: Ui<'a>) {
+        let draw_data = ui.render();
+        self.send_draw_data(&draw_data);
+    }
+
+    /// Sends draw data to all viewers. Viewers that can't be reached are dropped, and viewers
+    /// that haven't received the previous frame yet skip this one.
+    ///
+    /// Draw lists that are identical to the ones of the previous frame sent to a viewer are sent
+    /// as references to those, which keeps the bandwidth of a mostly static UI low.
+    pub fn send_draw_data(&mut self, draw_data: &DrawData) {
+        if self.clients.is_empty() {
+            return;
+        }
+        let owned = OwnedDrawData::from(draw_data);
+        let mut lists = Vec::with_capacity(owned.draw_lists.len());
+        for list in &owned.draw_lists {
+            let mut buf = Vec::new();
+            // Writing to a Vec can't fail
+            list.write_to(&mut buf).unwrap();
+            lists.push(Rc::new(buf));
+        }
+        let mut i = 0;
+        while i < self.clients.len() {
+            let result = {
+                let client = &mut self.clients[i];
+                client.send_frame(&self.font_texture, owned.display_size, &lists)
+            };
+            match result {
+                Ok((bytes, skipped)) => {
+                    self.bytes_sent += bytes as u64;
+                    self.frames_skipped += skipped as u64;
+                    i += 1;
+                }
+                Err(_) => {
+                    self.clients.remove(i);
+                }
+            }
+        }
+    }
+}
+
+fn encode_font_texture(imgui: &mut ImGui) -> io::Result<Vec<u8>> {
+    let id = imgui.font_texture_id();
+    let texture = imgui.prepare_texture(|handle| {
+        CapturedTexture {
+            id: id,
+            width: handle.width,
+            height: handle.height,
+            pixels: handle.pixels.to_vec(),
+        }
+    });
+    let mut buf = Vec::new();
+    texture.write_to(&mut buf)?;
+    Ok(buf)
+}
+
+fn receive_client_input(client: &mut Connection, imgui: &mut ImGui) -> io::Result<()> {
+    let open = client.reader.fill_nonblocking(&mut client.stream)?;
+    while let Some((kind, payload)) = client.reader.next_message()? {
+        if kind != MSG_INPUT {
+            return Err(invalid_data("Unexpected message from viewer"));
+        }
+        imgui.push_event(protocol::decode_event(&payload)?);
+    }
+    if open {
+        Ok(())
+    } else {
+        Err(ErrorKind::UnexpectedEof.into())
+    }
+}
+
+impl Connection {
+    /// Sends a frame unless the previous one is still being sent. Returns the number of bytes
+    /// written and whether the frame was skipped.
+    fn send_frame(
+        &mut self,
+        font_texture: &[u8],
+        display_size: (f32, f32),
+        lists: &[Rc<Vec<u8>>],
+    ) -> io::Result<(usize, bool)> {
+        let flushed = self.flush()?;
+        if !self.outgoing.is_empty() {
+            return Ok((flushed, true));
+        }
+        self.queue_frame(font_texture, display_size, lists);
+        Ok((flushed + self.flush()?, false))
+    }
+    /// Queues a frame, and the font texture if this client doesn't have it yet.
+    fn queue_frame(
+        &mut self,
+        font_texture: &[u8],
+        display_size: (f32, f32),
+        lists: &[Rc<Vec<u8>>],
+    ) {
+        // Writing to a Vec can't fail
+        if !self.font_texture_sent {
+            protocol::write_message(&mut self.outgoing, MSG_FONT_TEXTURE, font_texture).unwrap();
+            self.font_texture_sent = true;
+        }
+        let mut payload = Vec::new();
+        write_f32(&mut payload, display_size.0).unwrap();
+        write_f32(&mut payload, display_size.1).unwrap();
+        write_u32(&mut payload, lists.len() as u32).unwrap();
+        for (i, list) in lists.iter().enumerate() {
+            if self.last_lists.get(i) == Some(list) {
+                payload.push(LIST_UNCHANGED);
+            } else {
+                payload.push(LIST_FULL);
+                payload.extend_from_slice(list);
+            }
+        }
+        protocol::write_message(&mut self.outgoing, MSG_FRAME, &payload).unwrap();
+        self.last_lists = lists.to_vec();
+    }
+    /// Writes as much of the outgoing buffer as the socket accepts without blocking, and returns
+    /// the number of bytes written.
+    fn flush(&mut self) -> io::Result<usize> {
+        let mut written = 0;
+        while written < self.outgoing.len() {
+            match self.stream.write(&self.outgoing[written..]) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(n) => written += n,
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+        self.outgoing.drain(..written);
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{TcpListener, TcpStream};
+    use std::rc::Rc;
+
+    use protocol::MessageReader;
+    use super::Connection;
+
+    fn connect() -> (Connection, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let viewer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        stream.set_nonblocking(true).unwrap();
+        let connection = Connection {
+            stream: stream,
+            reader: MessageReader::default(),
+            font_texture_sent: false,
+            last_lists: Vec::new(),
+            outgoing: Vec::new(),
+        };
+        (connection, viewer)
+    }
+
+    #[test]
+    fn slow_viewers_skip_frames() {
+        let (mut connection, viewer) = connect();
+        // Much more than the socket buffers hold
+        let big_list = vec![Rc::new(vec![7; 64 * 1024 * 1024])];
+        let (_, skipped) = connection.send_frame(&[], (1.0, 1.0), &big_list).unwrap();
+        assert!(!skipped);
+        assert!(!connection.outgoing.is_empty());
+
+        let queued = connection.outgoing.len();
+        let (_, skipped) = connection.send_frame(&[], (1.0, 1.0), &big_list).unwrap();
+        assert!(skipped);
+        assert!(connection.outgoing.len() <= queued);
+        drop(viewer);
+    }
+
+    #[test]
+    fn unchanged_lists_are_shared() {
+        let (mut connection, _viewer) = connect();
+        let lists = vec![Rc::new(vec![1, 2, 3]), Rc::new(vec![4, 5])];
+        connection.send_frame(&[9], (1.0, 1.0), &lists).unwrap();
+        assert!(connection.outgoing.is_empty());
+        assert!(Rc::ptr_eq(&connection.last_lists[0], &lists[0]));
+
+        // Only the changed list follows in full
+        let next = vec![Rc::new(vec![1, 2, 3]), Rc::new(vec![6])];
+        connection.queue_frame(&[9], (1.0, 1.0), &next);
+        let mut reader = MessageReader::default();
+        reader.fill_nonblocking(&mut &connection.outgoing[..]).unwrap();
+        let (kind, payload) = reader.next_message().unwrap().unwrap();
+        assert_eq!(kind, ::protocol::MSG_FRAME);
+        assert_eq!(&payload[12..], &[::protocol::LIST_UNCHANGED, ::protocol::LIST_FULL, 6]);
+    }
+}
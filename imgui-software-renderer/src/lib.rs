@@ -0,0 +1,664 @@
+//! A renderer for [imgui](https://crates.io/crates/imgui) that rasterizes draw lists on the CPU
+//! into an in-memory RGBA image.
+//!
+//! It needs no window or GPU, so it can be used on headless build servers, e.g. for taking
+//! screenshots or comparing rendered frames in tests. Textures are sampled with nearest
+//! filtering and blended with straight alpha, like the glium renderer.
+//!
+//! The `snapshot` module uses it for comparing frames against reference images in tests.
+extern crate imgui;
+extern crate png;
+
+use imgui::{DrawCmd, DrawData, DrawList, FrameCapture, ImDrawVert, ImGui, TextureId, Textures,
+            Ui};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+pub mod snapshot;
+
+pub type RendererResult<T> = Result<T, RendererError>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RendererError {
+    BadTexture(TextureId),
+    /// A draw command refers to indices or vertices that the draw list doesn't have
+    InvalidDrawList,
+}
+
+impl fmt::Display for RendererError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::RendererError::*;
+        match *self {
+            BadTexture(ref t) => write!(f, "Bad texture ID: {}", t.id()),
+            InvalidDrawList => write!(f, "Draw list with out of range indices"),
+        }
+    }
+}
+
+/// An image with 8-bit RGBA pixels in row-major order, used both as a render target and for
+/// textures.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Creates a transparent black image.
+    pub fn new(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::filled(width, height, [0, 0, 0, 0])
+    }
+    /// Creates an image filled with a color.
+    pub fn filled(width: u32, height: u32, color: [u8; 4]) -> RgbaImage {
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+        for _ in 0..width as usize * height as usize {
+            pixels.extend_from_slice(&color);
+        }
+        RgbaImage {
+            width: width,
+            height: height,
+            pixels: pixels,
+        }
+    }
+    /// Creates an image from RGBA pixels.
+    ///
+    /// # Panics
+    /// Panics if the number of bytes doesn't match the size.
+    pub fn from_pixels(width: u32, height: u32, pixels: Vec<u8>) -> RgbaImage {
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize * 4,
+            "Pixel data doesn't match the image size"
+        );
+        RgbaImage {
+            width: width,
+            height: height,
+            pixels: pixels,
+        }
+    }
+    pub fn width(&self) -> u32 { self.width }
+    pub fn height(&self) -> u32 { self.height }
+    pub fn pixels(&self) -> &[u8] { &self.pixels }
+    pub fn pixels_mut(&mut self) -> &mut [u8] { &mut self.pixels }
+    pub fn into_pixels(self) -> Vec<u8> { self.pixels }
+    /// Returns the color of a pixel.
+    ///
+    /// # Panics
+    /// Panics if the pixel is outside the image.
+    pub fn get_pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        assert!(x < self.width && y < self.height, "Pixel is outside the image");
+        let i = self.offset(x, y);
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+    /// Sets the color of a pixel.
+    ///
+    /// # Panics
+    /// Panics if the pixel is outside the image.
+    pub fn put_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        assert!(x < self.width && y < self.height, "Pixel is outside the image");
+        let i = self.offset(x, y);
+        self.pixels[i..i + 4].copy_from_slice(&color);
+    }
+    /// Fills the whole image with a color.
+    pub fn clear(&mut self, color: [u8; 4]) {
+        for pixel in self.pixels.chunks_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+    /// Reads a PNG image. Images without an alpha channel are made opaque.
+    pub fn read_png<R: Read>(r: R) -> io::Result<RgbaImage> {
+        let (info, mut reader) = png::Decoder::new(r).read_info()?;
+        let mut buf = vec![0; info.buffer_size()];
+        reader.next_frame(&mut buf)?;
+        // 16-bit and palette images are converted to 8 bits per channel by the decoder
+        let pixels = match info.color_type {
+            png::ColorType::RGBA => buf,
+            png::ColorType::RGB => {
+                buf.chunks(3)
+                    .flat_map(|p| vec![p[0], p[1], p[2], 255])
+                    .collect()
+            }
+            png::ColorType::GrayscaleAlpha => {
+                buf.chunks(2)
+                    .flat_map(|p| vec![p[0], p[0], p[0], p[1]])
+                    .collect()
+            }
+            png::ColorType::Grayscale => buf.iter().flat_map(|&p| vec![p, p, p, 255]).collect(),
+            png::ColorType::Indexed => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Unexpected indexed PNG data",
+                ))
+            }
+        };
+        Ok(RgbaImage::from_pixels(info.width, info.height, pixels))
+    }
+    /// Loads a PNG image from a file.
+    pub fn load_png<P: AsRef<Path>>(path: P) -> io::Result<RgbaImage> {
+        RgbaImage::read_png(BufReader::new(File::open(path)?))
+    }
+    /// Writes the image in PNG format.
+    pub fn write_png<W: Write>(&self, w: W) -> io::Result<()> {
+        use png::HasParameters;
+
+        let mut encoder = png::Encoder::new(w, self.width, self.height);
+        encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+    /// Saves the image as a PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+    fn offset(&self, x: u32, y: u32) -> usize { (y as usize * self.width as usize + x as usize) * 4 }
+}
+
+pub struct Renderer {
+    textures: Textures<RgbaImage>,
+}
+
+impl Renderer {
+    /// Creates a renderer without a font texture, e.g. for rendering captures with
+    /// `render_capture`, which bring their own.
+    pub fn new() -> Renderer {
+        Renderer {
+            textures: Textures::new(),
+        }
+    }
+    pub fn init(imgui: &mut ImGui) -> Renderer {
+        let mut textures = Textures::new();
+        let font_texture = copy_font_texture(imgui);
+        imgui.set_font_texture_id(textures.insert(font_texture));
+        Renderer { textures: textures }
+    }
+
+    /// Rebuilds the font atlas texture and copies it again.
+    ///
+    /// This needs to be called after fonts have been changed.
+    pub fn reload_font_texture(&mut self, imgui: &mut ImGui) {
+        let texture = copy_font_texture(imgui);
+        let id = imgui.font_texture_id();
+        self.textures.replace(id, texture);
+    }
+
+    /// Returns the texture registry, which can be used to register application textures for
+    /// drawing with imgui.
+    pub fn textures(&mut self) -> &mut Textures<RgbaImage> { &mut self.textures }
+
+    /// Renders a frame into the target image. The display size of the frame is scaled to the
+    /// size of the image.
+    pub fn render<'a>(&mut self, target: &mut RgbaImage, ui: Ui<'a>) -> RendererResult<()> {
+        let draw_data = ui.render();
+        self.render_draw_data(target, &draw_data)
+    }
+
+    /// Renders draw data into the target image, like `render`.
+    pub fn render_draw_data(
+        &mut self,
+        target: &mut RgbaImage,
+        draw_data: &DrawData,
+    ) -> RendererResult<()> {
+        let display_size = draw_data.display_size();
+        if display_size.0 == 0.0 || display_size.1 == 0.0 {
+            return Ok(());
+        }
+        let scale = (
+            target.width as f32 / display_size.0,
+            target.height as f32 / display_size.1,
+        );
+        for draw_list in draw_data {
+            self.render_draw_list(target, &draw_list, scale)?;
+        }
+        Ok(())
+    }
+
+    /// Renders a captured frame into the target image, like `render`.
+    ///
+    /// The font texture of the capture is registered with its original texture id, replacing
+    /// any texture with the same id. Other textures used by the capture need to be registered
+    /// through `textures` beforehand.
+    pub fn render_capture(
+        &mut self,
+        target: &mut RgbaImage,
+        capture: &mut FrameCapture,
+    ) -> RendererResult<()> {
+        if let Some(ref texture) = capture.font_texture {
+            let image = RgbaImage::from_pixels(texture.width, texture.height, texture.pixels.clone());
+            self.textures.replace(texture.id, image);
+        }
+        self.render_draw_data(target, &capture.draw_data.draw_data())
+    }
+
+    fn render_draw_list(
+        &mut self,
+        target: &mut RgbaImage,
+        draw_list: &DrawList,
+        scale: (f32, f32),
+    ) -> RendererResult<()> {
+        let mut idx_start = 0;
+        for cmd in draw_list.commands() {
+            let (count, clip_rect, texture_id) = match cmd {
+                DrawCmd::Elements {
+                    count,
+                    clip_rect,
+                    texture_id,
+                } => (count, clip_rect, texture_id),
+                // There's no render state besides the target
+                DrawCmd::ResetRenderState => continue,
+                DrawCmd::Callback(callback) => {
+                    callback.invoke();
+                    continue;
+                }
+            };
+            let texture = self.textures.get(texture_id).ok_or(
+                RendererError::BadTexture(texture_id),
+            )?;
+
+            let idx_end = idx_start + count;
+            let indices = draw_list.idx_buffer.get(idx_start..idx_end).ok_or(
+                RendererError::InvalidDrawList,
+            )?;
+            let clip = Clip::new(clip_rect, scale, target);
+            for triangle in indices.chunks(3) {
+                if triangle.len() < 3 {
+                    break;
+                }
+                let vertex = |i: usize| {
+                    draw_list
+                        .vtx_buffer
+                        .get(triangle[i] as usize)
+                        .map(|v| Vertex::new(v, scale))
+                        .ok_or(RendererError::InvalidDrawList)
+                };
+                draw_triangle(target, texture, &clip, [vertex(0)?, vertex(1)?, vertex(2)?]);
+            }
+
+            idx_start = idx_end;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Renderer {
+    fn default() -> Renderer { Renderer::new() }
+}
+
+fn copy_font_texture(imgui: &mut ImGui) -> RgbaImage {
+    imgui.prepare_texture(|handle| {
+        RgbaImage::from_pixels(handle.width, handle.height, handle.pixels.to_vec())
+    })
+}
+
+/// A clip rectangle in pixels. Pixels whose centers are inside are drawn.
+struct Clip {
+    x0: u32,
+    y0: u32,
+    x1: u32,
+    y1: u32,
+}
+
+impl Clip {
+    fn new(clip_rect: [f32; 4], scale: (f32, f32), target: &RgbaImage) -> Clip {
+        let round = |v: f32, max: u32| if v <= 0.0 {
+            0
+        } else {
+            (v.round() as u32).min(max)
+        };
+        Clip {
+            x0: round(clip_rect[0] * scale.0, target.width),
+            y0: round(clip_rect[1] * scale.1, target.height),
+            x1: round(clip_rect[2] * scale.0, target.width),
+            y1: round(clip_rect[3] * scale.1, target.height),
+        }
+    }
+}
+
+/// A vertex in pixel coordinates, with its color as floats in 0..1.
+#[derive(Copy, Clone)]
+struct Vertex {
+    x: f32,
+    y: f32,
+    u: f32,
+    v: f32,
+    color: [f32; 4],
+}
+
+impl Vertex {
+    fn new(v: &ImDrawVert, scale: (f32, f32)) -> Vertex {
+        let channel = |shift: u32| ((v.col >> shift) & 0xff) as f32 / 255.0;
+        Vertex {
+            x: v.pos.x * scale.0,
+            y: v.pos.y * scale.1,
+            u: v.uv.x,
+            v: v.uv.y,
+            color: [channel(0), channel(8), channel(16), channel(24)],
+        }
+    }
+}
+
+/// Returns twice the signed area of the triangle (a, b, p). It's positive if p is on the inner
+/// side of the edge from a to b in a triangle with positive area.
+fn edge(a: &Vertex, b: &Vertex, x: f32, y: f32) -> f32 {
+    (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+}
+
+/// Returns true if pixels exactly on the edge from a to b belong to the triangle. Only top and
+/// left edges are included, so pixels on an edge shared by two triangles are drawn once.
+fn is_top_left(a: &Vertex, b: &Vertex) -> bool {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    (dy == 0.0 && dx > 0.0) || dy < 0.0
+}
+
+fn draw_triangle(target: &mut RgbaImage, texture: &RgbaImage, clip: &Clip, v: [Vertex; 3]) {
+    let mut v = v;
+    let mut area = edge(&v[0], &v[1], v[2].x, v[2].y);
+    if area == 0.0 {
+        return;
+    }
+    if area < 0.0 {
+        v.swap(1, 2);
+        area = -area;
+    }
+
+    let min_x = v[0].x.min(v[1].x).min(v[2].x).floor().max(clip.x0 as f32) as u32;
+    let min_y = v[0].y.min(v[1].y).min(v[2].y).floor().max(clip.y0 as f32) as u32;
+    let max_x = (v[0].x.max(v[1].x).max(v[2].x).ceil().max(0.0) as u32).min(clip.x1);
+    let max_y = (v[0].y.max(v[1].y).max(v[2].y).ceil().max(0.0) as u32).min(clip.y1);
+
+    let top_left = [
+        is_top_left(&v[1], &v[2]),
+        is_top_left(&v[2], &v[0]),
+        is_top_left(&v[0], &v[1]),
+    ];
+    let inside = |w: f32, top_left: bool| w > 0.0 || (w == 0.0 && top_left);
+
+    for y in min_y..max_y {
+        let py = y as f32 + 0.5;
+        for x in min_x..max_x {
+            let px = x as f32 + 0.5;
+            let w0 = edge(&v[1], &v[2], px, py);
+            let w1 = edge(&v[2], &v[0], px, py);
+            let w2 = edge(&v[0], &v[1], px, py);
+            if !(inside(w0, top_left[0]) && inside(w1, top_left[1]) && inside(w2, top_left[2])) {
+                continue;
+            }
+            let (l0, l1, l2) = (w0 / area, w1 / area, w2 / area);
+            let interpolate = |a: f32, b: f32, c: f32| a * l0 + b * l1 + c * l2;
+
+            let u = interpolate(v[0].u, v[1].u, v[2].u);
+            let tv = interpolate(v[0].v, v[1].v, v[2].v);
+            let texel = sample(texture, u, tv);
+            let mut src = [0.0; 4];
+            for c in 0..4 {
+                let color = interpolate(v[0].color[c], v[1].color[c], v[2].color[c]);
+                src[c] = color * texel[c] as f32 / 255.0;
+            }
+            blend(target, x, y, src);
+        }
+    }
+}
+
+/// Samples a texture with nearest filtering, clamping coordinates to the edges.
+fn sample(texture: &RgbaImage, u: f32, v: f32) -> [u8; 4] {
+    if texture.width == 0 || texture.height == 0 {
+        return [255, 255, 255, 255];
+    }
+    let coord = |t: f32, size: u32| {
+        let t = (t * size as f32).floor();
+        if t <= 0.0 {
+            0
+        } else {
+            (t as u32).min(size - 1)
+        }
+    };
+    texture.get_pixel(coord(u, texture.width), coord(v, texture.height))
+}
+
+/// Blends a color over a pixel with straight alpha blending.
+fn blend(target: &mut RgbaImage, x: u32, y: u32, src: [f32; 4]) {
+    let i = target.offset(x, y);
+    let dst = &mut target.pixels[i..i + 4];
+    let alpha = src[3].max(0.0).min(1.0);
+    for c in 0..3 {
+        let value = src[c] * alpha + dst[c] as f32 / 255.0 * (1.0 - alpha);
+        dst[c] = to_u8(value);
+    }
+    let value = alpha + dst[3] as f32 / 255.0 * (1.0 - alpha);
+    dst[3] = to_u8(value);
+}
+
+fn to_u8(value: f32) -> u8 { (value.max(0.0).min(1.0) * 255.0 + 0.5) as u8 }
+
+#[cfg(test)]
+mod tests {
+    use imgui::{sys, ImDrawVert, ImVec2, OwnedDrawData, OwnedDrawList, TextureId};
+    use std::io::Cursor;
+
+    use super::*;
+
+    const WHITE: u32 = 0xffff_ffff;
+
+    fn renderer_with(texture: RgbaImage) -> (Renderer, TextureId) {
+        let mut renderer = Renderer::new();
+        let id = renderer.textures().insert(texture);
+        (renderer, id)
+    }
+
+    fn vertex(x: f32, y: f32, u: f32, v: f32, col: u32) -> ImDrawVert {
+        ImDrawVert {
+            pos: ImVec2::new(x, y),
+            uv: ImVec2::new(u, v),
+            col: col,
+        }
+    }
+
+    /// A quad from (x0, y0) to (x1, y1) with uv coordinates from 0 to 1, split into two
+    /// triangles along the diagonal from (x0, y0) to (x1, y1).
+    fn quad(rect: [f32; 4], clip_rect: [f32; 4], col: u32, texture_id: TextureId) -> OwnedDrawList {
+        let (x0, y0, x1, y1) = (rect[0], rect[1], rect[2], rect[3]);
+        OwnedDrawList {
+            cmd_buffer: vec![
+                sys::ImDrawCmd {
+                    elem_count: 6,
+                    clip_rect: clip_rect.into(),
+                    texture_id: texture_id.id() as *mut _,
+                    user_callback: None,
+                    user_callback_data: ::std::ptr::null_mut(),
+                },
+            ],
+            idx_buffer: vec![0, 1, 2, 0, 2, 3],
+            vtx_buffer: vec![
+                vertex(x0, y0, 0.0, 0.0, col),
+                vertex(x1, y0, 1.0, 0.0, col),
+                vertex(x1, y1, 1.0, 1.0, col),
+                vertex(x0, y1, 0.0, 1.0, col),
+            ],
+        }
+    }
+
+    fn render(renderer: &mut Renderer, target: &mut RgbaImage, lists: Vec<OwnedDrawList>) {
+        let size = (target.width() as f32, target.height() as f32);
+        let mut draw_data = OwnedDrawData::new(size);
+        draw_data.draw_lists = lists;
+        renderer.render_draw_data(target, &draw_data.draw_data()).unwrap();
+    }
+
+    fn white_texture() -> RgbaImage { RgbaImage::filled(1, 1, [255, 255, 255, 255]) }
+
+    #[test]
+    fn quad_fills_exactly_its_pixels() {
+        let (mut renderer, id) = renderer_with(white_texture());
+        let mut target = RgbaImage::new(8, 8);
+        let full = [0.0, 0.0, 8.0, 8.0];
+        render(&mut renderer, &mut target, vec![quad([2.0, 2.0, 6.0, 5.0], full, WHITE, id)]);
+        for y in 0..8 {
+            for x in 0..8 {
+                let inside = x >= 2 && x < 6 && y >= 2 && y < 5;
+                let expected = if inside { [255; 4] } else { [0; 4] };
+                assert_eq!(target.get_pixel(x, y), expected, "pixel {}, {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn shared_edges_are_drawn_once() {
+        let (mut renderer, id) = renderer_with(white_texture());
+        // The diagonal shared by both triangles passes through the centers of the pixels
+        // (0, 0), (1, 1), ..., so drawing them twice would blend the color twice
+        let mut target = RgbaImage::filled(4, 4, [0, 0, 0, 255]);
+        let full = [0.0, 0.0, 4.0, 4.0];
+        let half_white = 0x80ff_ffff;
+        render(&mut renderer, &mut target, vec![quad(full, full, half_white, id)]);
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(target.get_pixel(x, y), [128, 128, 128, 255], "pixel {}, {}", x, y);
+            }
+        }
+
+        // Two adjacent quads share a vertical edge
+        let mut target = RgbaImage::filled(4, 2, [0, 0, 0, 255]);
+        let lists = vec![
+            quad([0.0, 0.0, 2.0, 2.0], [0.0, 0.0, 4.0, 2.0], half_white, id),
+            quad([2.0, 0.0, 4.0, 2.0], [0.0, 0.0, 4.0, 2.0], half_white, id),
+        ];
+        render(&mut renderer, &mut target, lists);
+        assert!(target.pixels().chunks(4).all(|p| p == [128, 128, 128, 255]));
+    }
+
+    #[test]
+    fn clipping() {
+        let (mut renderer, id) = renderer_with(white_texture());
+        let mut target = RgbaImage::new(6, 6);
+        let clip_rect = [1.0, 2.0, 4.0, 3.0];
+        render(&mut renderer, &mut target, vec![quad([0.0, 0.0, 6.0, 6.0], clip_rect, WHITE, id)]);
+        for y in 0..6 {
+            for x in 0..6 {
+                let inside = x >= 1 && x < 4 && y == 2;
+                assert_eq!(target.get_pixel(x, y)[3] != 0, inside, "pixel {}, {}", x, y);
+            }
+        }
+
+        // Clip rectangles are scaled with the display size like the vertices
+        let mut target = RgbaImage::new(12, 12);
+        let mut draw_data = OwnedDrawData::new((6.0, 6.0));
+        draw_data.draw_lists = vec![quad([0.0, 0.0, 6.0, 6.0], clip_rect, WHITE, id)];
+        renderer.render_draw_data(&mut target, &draw_data.draw_data()).unwrap();
+        let drawn = target.pixels().chunks(4).filter(|p| p[3] != 0).count();
+        assert_eq!(drawn, 6 * 2);
+        assert_eq!(target.get_pixel(2, 4), [255; 4]);
+        assert_eq!(target.get_pixel(7, 5), [255; 4]);
+        assert_eq!(target.get_pixel(8, 4), [0; 4]);
+    }
+
+    #[test]
+    fn nearest_sampling() {
+        let colors = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [255, 255, 0, 255]];
+        let mut texture = RgbaImage::new(2, 2);
+        texture.put_pixel(0, 0, colors[0]);
+        texture.put_pixel(1, 0, colors[1]);
+        texture.put_pixel(0, 1, colors[2]);
+        texture.put_pixel(1, 1, colors[3]);
+        let (mut renderer, id) = renderer_with(texture.clone());
+        let mut target = RgbaImage::new(4, 4);
+        let full = [0.0, 0.0, 4.0, 4.0];
+        render(&mut renderer, &mut target, vec![quad(full, full, WHITE, id)]);
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = colors[(y / 2 * 2 + x / 2) as usize];
+                assert_eq!(target.get_pixel(x, y), expected, "pixel {}, {}", x, y);
+            }
+        }
+
+        // Coordinates outside the texture are clamped to its edges
+        assert_eq!(sample(&texture, -0.5, 0.25), colors[0]);
+        assert_eq!(sample(&texture, 1.5, 0.25), colors[1]);
+        assert_eq!(sample(&texture, 0.75, 1.0), colors[3]);
+    }
+
+    #[test]
+    fn blending() {
+        let mut target = RgbaImage::filled(3, 1, [0, 0, 255, 255]);
+        blend(&mut target, 0, 0, [1.0, 0.0, 0.0, 0.5]);
+        assert_eq!(target.get_pixel(0, 0), [128, 0, 128, 255]);
+        blend(&mut target, 1, 0, [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(target.get_pixel(1, 0), [255, 255, 255, 255]);
+        blend(&mut target, 2, 0, [1.0, 1.0, 1.0, 0.0]);
+        assert_eq!(target.get_pixel(2, 0), [0, 0, 255, 255]);
+
+        // The alpha of a transparent target accumulates
+        let mut target = RgbaImage::new(1, 1);
+        blend(&mut target, 0, 0, [1.0, 1.0, 1.0, 0.5]);
+        blend(&mut target, 0, 0, [1.0, 1.0, 1.0, 0.5]);
+        assert_eq!(target.get_pixel(0, 0)[3], 192);
+
+        // Vertex colors multiply the texture
+        let (mut renderer, id) = renderer_with(RgbaImage::filled(1, 1, [255, 128, 0, 255]));
+        let mut target = RgbaImage::new(1, 1);
+        let full = [0.0, 0.0, 1.0, 1.0];
+        render(&mut renderer, &mut target, vec![quad(full, full, 0xff00_80ff, id)]);
+        assert_eq!(target.get_pixel(0, 0), [255, 64, 0, 255]);
+    }
+
+    #[test]
+    fn invalid_draw_lists() {
+        let (mut renderer, id) = renderer_with(white_texture());
+        let mut target = RgbaImage::new(4, 4);
+        let full = [0.0, 0.0, 4.0, 4.0];
+        let mut render_list = |list: OwnedDrawList| {
+            let mut draw_data = OwnedDrawData::new((4.0, 4.0));
+            draw_data.draw_lists = vec![list];
+            renderer.render_draw_data(&mut target, &draw_data.draw_data())
+        };
+
+        let mut list = quad(full, full, WHITE, id);
+        list.idx_buffer[5] = 4;
+        assert_eq!(render_list(list), Err(RendererError::InvalidDrawList));
+
+        let mut list = quad(full, full, WHITE, id);
+        list.cmd_buffer[0].elem_count = 9;
+        assert_eq!(render_list(list), Err(RendererError::InvalidDrawList));
+    }
+
+    fn encode_png(width: u32, height: u32, color_type: png::ColorType, data: &[u8]) -> Vec<u8> {
+        use png::HasParameters;
+
+        let mut buf = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut buf, width, height);
+            encoder.set(color_type).set(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(data).unwrap();
+        }
+        buf
+    }
+
+    #[test]
+    fn png_conversions() {
+        let rgb = encode_png(2, 1, png::ColorType::RGB, &[1, 2, 3, 4, 5, 6]);
+        let image = RgbaImage::read_png(Cursor::new(rgb)).unwrap();
+        assert_eq!(image.pixels(), &[1, 2, 3, 255, 4, 5, 6, 255]);
+
+        let gray = encode_png(2, 1, png::ColorType::Grayscale, &[7, 8]);
+        let image = RgbaImage::read_png(Cursor::new(gray)).unwrap();
+        assert_eq!(image.pixels(), &[7, 7, 7, 255, 8, 8, 8, 255]);
+
+        let gray_alpha = encode_png(1, 1, png::ColorType::GrayscaleAlpha, &[9, 10]);
+        let image = RgbaImage::read_png(Cursor::new(gray_alpha)).unwrap();
+        assert_eq!(image.pixels(), &[9, 9, 9, 10]);
+
+        let original = RgbaImage::from_pixels(1, 2, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut rgba = Vec::new();
+        original.write_png(&mut rgba).unwrap();
+        assert_eq!(RgbaImage::read_png(Cursor::new(rgba)).unwrap(), original);
+    }
+}
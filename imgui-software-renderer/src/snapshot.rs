@@ -0,0 +1,452 @@
+//! Golden-image snapshot testing.
+//!
+//! `SnapshotRenderer` renders frames of a `TestHarness` into images, and `Snapshot` compares
+//! them against reference PNG files. When a comparison fails, the rendered image and an image
+//! marking the differing pixels in red are written next to the reference file, with the
+//! extensions `.actual.png` and `.diff.png`.
+//!
+//! Reference files are created or updated by running the tests with the environment variable
+//! `IMGUI_BLESS_SNAPSHOTS=1`.
+//!
+//! # Example
+//! ```rust,no_run
+//! extern crate imgui;
+//! extern crate imgui_software_renderer;
+//!
+//! use imgui::ImGuiCond;
+//! use imgui::testing::TestHarness;
+//! use imgui_software_renderer::snapshot::{Snapshot, SnapshotRenderer};
+//!
+//! # fn main() {
+//! let mut harness = TestHarness::new();
+//! harness.set_display_size(320, 240);
+//! let mut snapshots = SnapshotRenderer::new(harness);
+//! let image = snapshots
+//!     .render(|ui| {
+//!         ui.window("Hello")
+//!             .position((10.0, 10.0), ImGuiCond::Always)
+//!             .build(|| ui.text("Hello world!"));
+//!     })
+//!     .unwrap();
+//! Snapshot::new("tests/snapshots/hello.png")
+//!     .tolerance(2)
+//!     .assert_matches(&image);
+//! # }
+//! ```
+use imgui::Ui;
+use imgui::testing::TestHarness;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{Renderer, RendererResult, RgbaImage};
+
+/// The environment variable that makes `Snapshot` write the rendered image as the new
+/// reference instead of comparing against it.
+pub const BLESS_ENV_VAR: &'static str = "IMGUI_BLESS_SNAPSHOTS";
+
+/// The background color of rendered frames, the clear color of the imgui examples.
+pub const DEFAULT_CLEAR_COLOR: [u8; 4] = [115, 140, 153, 255];
+
+/// Renders the frames of a test harness into images.
+pub struct SnapshotRenderer {
+    harness: TestHarness,
+    renderer: Renderer,
+    clear_color: [u8; 4],
+}
+
+impl SnapshotRenderer {
+    pub fn new(mut harness: TestHarness) -> SnapshotRenderer {
+        let renderer = Renderer::init(harness.imgui_mut());
+        SnapshotRenderer {
+            harness: harness,
+            renderer: renderer,
+            clear_color: DEFAULT_CLEAR_COLOR,
+        }
+    }
+    pub fn harness(&self) -> &TestHarness { &self.harness }
+    /// Returns the harness, e.g. for scripting input or running frames that aren't rendered.
+    pub fn harness_mut(&mut self) -> &mut TestHarness { &mut self.harness }
+    pub fn renderer_mut(&mut self) -> &mut Renderer { &mut self.renderer }
+    pub fn clear_color(&self) -> [u8; 4] { self.clear_color }
+    pub fn set_clear_color(&mut self, color: [u8; 4]) { self.clear_color = color; }
+
+    /// Runs one frame and renders it into an image with the display size of the harness.
+    pub fn render<F: FnOnce(&Ui)>(&mut self, f: F) -> RendererResult<RgbaImage> {
+        let (width, height) = self.harness.display_size();
+        let mut image = RgbaImage::filled(width, height, self.clear_color);
+        {
+            let renderer = &mut self.renderer;
+            let target = &mut image;
+            self.harness.render_frame(f, |ui| renderer.render(target, ui))?;
+        }
+        Ok(image)
+    }
+}
+
+/// The result of comparing two images of the same size.
+#[derive(Clone, Debug)]
+pub struct ImageDiff {
+    /// Number of pixels with a channel that differs by more than the tolerance
+    pub differing_pixels: usize,
+    /// Largest difference of any channel of any pixel
+    pub max_difference: u8,
+    /// The expected image faded to gray, with the differing pixels in red
+    pub image: RgbaImage,
+}
+
+/// Compares two images channel by channel, allowing each channel to differ by at most
+/// `tolerance`.
+///
+/// # Panics
+/// Panics if the images have different sizes.
+pub fn compare_images(expected: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> ImageDiff {
+    assert!(
+        expected.width() == actual.width() && expected.height() == actual.height(),
+        "Images have different sizes"
+    );
+    let mut diff = ImageDiff {
+        differing_pixels: 0,
+        max_difference: 0,
+        image: RgbaImage::new(expected.width(), expected.height()),
+    };
+    {
+        let pixels = expected
+            .pixels()
+            .chunks(4)
+            .zip(actual.pixels().chunks(4))
+            .zip(diff.image.pixels_mut().chunks_mut(4));
+        for ((e, a), out) in pixels {
+            let difference = e.iter()
+                .zip(a)
+                .map(|(&e, &a)| if e > a { e - a } else { a - e })
+                .max()
+                .unwrap_or(0);
+            if difference > diff.max_difference {
+                diff.max_difference = difference;
+            }
+            if difference > tolerance {
+                diff.differing_pixels += 1;
+                out.copy_from_slice(&[255, 0, 0, 255]);
+            } else {
+                let luma = (e[0] as u32 * 3 + e[1] as u32 * 6 + e[2] as u32) / 10;
+                let faded = (128 + luma / 2) as u8;
+                out.copy_from_slice(&[faded, faded, faded, 255]);
+            }
+        }
+    }
+    diff
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The reference image doesn't exist yet
+    Missing(PathBuf),
+    /// The rendered image has a different size than the reference image
+    SizeMismatch {
+        path: PathBuf,
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// Too many pixels differ from the reference image
+    Mismatch {
+        path: PathBuf,
+        differing_pixels: usize,
+        max_difference: u8,
+    },
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::SnapshotError::*;
+        match *self {
+            Missing(ref path) => write!(
+                f,
+                "Snapshot {} doesn't exist, run with {}=1 to create it",
+                path.display(),
+                BLESS_ENV_VAR
+            ),
+            SizeMismatch {
+                ref path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Snapshot {} is {}x{}, but the rendered image is {}x{} (see {})",
+                path.display(),
+                expected.0,
+                expected.1,
+                actual.0,
+                actual.1,
+                sibling_path(path, "actual").display()
+            ),
+            Mismatch {
+                ref path,
+                differing_pixels,
+                max_difference,
+            } => write!(
+                f,
+                "Snapshot {} doesn't match: {} pixels differ, by up to {} (see {})",
+                path.display(),
+                differing_pixels,
+                max_difference,
+                sibling_path(path, "diff").display()
+            ),
+            Io(ref path, ref err) => write!(f, "Snapshot {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn description(&self) -> &str {
+        use self::SnapshotError::*;
+        match *self {
+            Missing(_) => "snapshot doesn't exist",
+            SizeMismatch { .. } => "snapshot has a different size",
+            Mismatch { .. } => "snapshot doesn't match",
+            Io(..) => "snapshot I/O error",
+        }
+    }
+}
+
+/// A reference image that rendered images are compared against.
+#[must_use]
+pub struct Snapshot {
+    path: PathBuf,
+    tolerance: u8,
+    max_differing_pixels: usize,
+}
+
+impl Snapshot {
+    /// Creates a snapshot stored as a PNG file at the given path. By default, every pixel has to
+    /// match exactly.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Snapshot {
+        Snapshot {
+            path: path.into(),
+            tolerance: 0,
+            max_differing_pixels: 0,
+        }
+    }
+    /// Sets how much each color channel of a pixel may differ from the reference.
+    #[inline]
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+    /// Sets how many pixels may differ by more than the tolerance.
+    #[inline]
+    pub fn max_differing_pixels(mut self, count: usize) -> Self {
+        self.max_differing_pixels = count;
+        self
+    }
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Compares the image against the reference, or replaces the reference if `BLESS_ENV_VAR`
+    /// is set.
+    ///
+    /// On failure, the image is saved next to the reference with the extension `.actual.png`,
+    /// and if the sizes match, the differences with the extension `.diff.png`. These files are
+    /// removed again when the comparison succeeds.
+    pub fn check(&self, image: &RgbaImage) -> Result<(), SnapshotError> {
+        let actual_path = sibling_path(&self.path, "actual");
+        let diff_path = sibling_path(&self.path, "diff");
+        if bless_enabled() {
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir).map_err(|e| SnapshotError::Io(dir.to_owned(), e))?;
+            }
+            image
+                .save_png(&self.path)
+                .map_err(|e| SnapshotError::Io(self.path.clone(), e))?;
+            remove_stale(&actual_path, &diff_path);
+            return Ok(());
+        }
+        let expected = match RgbaImage::load_png(&self.path) {
+            Ok(expected) => expected,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(SnapshotError::Missing(self.path.clone()))
+            }
+            Err(e) => return Err(SnapshotError::Io(self.path.clone(), e)),
+        };
+        let save = |image: &RgbaImage, path: &Path| {
+            image
+                .save_png(path)
+                .map_err(|e| SnapshotError::Io(path.to_owned(), e))
+        };
+        if expected.width() != image.width() || expected.height() != image.height() {
+            save(image, &actual_path)?;
+            return Err(SnapshotError::SizeMismatch {
+                path: self.path.clone(),
+                expected: (expected.width(), expected.height()),
+                actual: (image.width(), image.height()),
+            });
+        }
+        let diff = compare_images(&expected, image, self.tolerance);
+        if diff.differing_pixels > self.max_differing_pixels {
+            save(image, &actual_path)?;
+            save(&diff.image, &diff_path)?;
+            return Err(SnapshotError::Mismatch {
+                path: self.path.clone(),
+                differing_pixels: diff.differing_pixels,
+                max_difference: diff.max_difference,
+            });
+        }
+        remove_stale(&actual_path, &diff_path);
+        Ok(())
+    }
+    /// Compares the image against the reference like `check`.
+    ///
+    /// # Panics
+    /// Panics with a description of the failure if the image doesn't match.
+    pub fn assert_matches(&self, image: &RgbaImage) {
+        if let Err(e) = self.check(image) {
+            panic!("{}", e);
+        }
+    }
+}
+
+fn bless_enabled() -> bool {
+    match env::var(BLESS_ENV_VAR) {
+        Ok(value) => !value.is_empty() && value != "0",
+        Err(_) => false,
+    }
+}
+
+/// Returns e.g. `button.actual.png` for `button.png`.
+fn sibling_path(path: &Path, kind: &str) -> PathBuf {
+    let stem = path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.{}.png", stem, kind))
+}
+
+fn remove_stale(actual_path: &Path, diff_path: &Path) {
+    let _ = fs::remove_file(actual_path);
+    let _ = fs::remove_file(diff_path);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    fn image(pixels: &[[u8; 4]]) -> RgbaImage {
+        let data = pixels.iter().flat_map(|p| p.iter().cloned()).collect();
+        RgbaImage::from_pixels(pixels.len() as u32, 1, data)
+    }
+
+    #[test]
+    fn identical_images() {
+        let expected = image(&[[10, 20, 30, 255], [0, 0, 0, 0]]);
+        let diff = compare_images(&expected, &expected.clone(), 0);
+        assert_eq!(diff.differing_pixels, 0);
+        assert_eq!(diff.max_difference, 0);
+        // Matching pixels are faded to gray
+        assert_eq!(diff.image.get_pixel(0, 0), [137, 137, 137, 255]);
+        assert_eq!(diff.image.get_pixel(1, 0), [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn differences_and_tolerance() {
+        let expected = image(&[[10, 20, 30, 255], [100, 100, 100, 255], [0, 0, 0, 255]]);
+        let actual = image(&[[12, 20, 30, 255], [100, 100, 100, 250], [0, 0, 0, 255]]);
+        let diff = compare_images(&expected, &actual, 2);
+        assert_eq!(diff.differing_pixels, 1);
+        assert_eq!(diff.max_difference, 5);
+        assert_eq!(diff.image.get_pixel(1, 0), [255, 0, 0, 255]);
+        assert_ne!(diff.image.get_pixel(0, 0), [255, 0, 0, 255]);
+
+        let diff = compare_images(&expected, &actual, 5);
+        assert_eq!(diff.differing_pixels, 0);
+        assert_eq!(diff.max_difference, 5);
+        // Differences are symmetric
+        let diff = compare_images(&actual, &expected, 1);
+        assert_eq!(diff.differing_pixels, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Images have different sizes")]
+    fn different_sizes() { compare_images(&RgbaImage::new(1, 2), &RgbaImage::new(2, 1), 0); }
+
+    #[test]
+    fn sibling_paths() {
+        let path = Path::new("tests/snapshots/button.png");
+        assert_eq!(
+            sibling_path(path, "actual"),
+            PathBuf::from("tests/snapshots/button.actual.png")
+        );
+        assert_eq!(sibling_path(path, "diff"), PathBuf::from("tests/snapshots/button.diff.png"));
+        assert_eq!(sibling_path(Path::new("button"), "diff"), PathBuf::from("button.diff.png"));
+    }
+
+    // The only test that sets the bless variable, since tests share the environment
+    #[test]
+    fn bless_and_cleanup() {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let name = format!("imgui-snapshot-test-{}-{}", now.as_secs(), now.subsec_nanos());
+        let dir = env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join("frame.png");
+        let actual_path = sibling_path(&path, "actual");
+        let diff_path = sibling_path(&path, "diff");
+        let snapshot = Snapshot::new(path.clone());
+        let original = image(&[[1, 2, 3, 255], [4, 5, 6, 255]]);
+        let changed = image(&[[1, 2, 3, 255], [40, 5, 6, 255]]);
+
+        env::remove_var(BLESS_ENV_VAR);
+        match snapshot.check(&original) {
+            Err(SnapshotError::Missing(ref missing)) => assert_eq!(missing, &path),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        for &value in &["", "0"] {
+            env::set_var(BLESS_ENV_VAR, value);
+            assert!(!bless_enabled());
+        }
+
+        // Blessing creates the reference and its directories
+        env::set_var(BLESS_ENV_VAR, "1");
+        assert!(bless_enabled());
+        snapshot.check(&original).unwrap();
+        env::remove_var(BLESS_ENV_VAR);
+        assert_eq!(RgbaImage::load_png(&path).unwrap(), original);
+        snapshot.check(&original).unwrap();
+
+        match snapshot.check(&changed) {
+            Err(SnapshotError::Mismatch {
+                differing_pixels,
+                max_difference,
+                ..
+            }) => assert_eq!((differing_pixels, max_difference), (1, 36)),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        assert_eq!(RgbaImage::load_png(&actual_path).unwrap(), changed);
+        assert_eq!(RgbaImage::load_png(&diff_path).unwrap().get_pixel(1, 0), [255, 0, 0, 255]);
+        Snapshot::new(path.clone()).max_differing_pixels(1).check(&changed).unwrap();
+        assert!(!actual_path.exists() && !diff_path.exists());
+
+        match snapshot.check(&RgbaImage::new(1, 1)) {
+            Err(SnapshotError::SizeMismatch {
+                expected, actual, ..
+            }) => assert_eq!((expected, actual), ((2, 1), (1, 1))),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        assert!(actual_path.exists() && !diff_path.exists());
+
+        // Blessing replaces the reference and removes the files of the failed comparison
+        snapshot.check(&changed).unwrap_err();
+        env::set_var(BLESS_ENV_VAR, "yes");
+        snapshot.check(&changed).unwrap();
+        env::remove_var(BLESS_ENV_VAR);
+        assert!(!actual_path.exists() && !diff_path.exists());
+        snapshot.check(&changed).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,118 @@
+extern crate imgui;
+extern crate imgui_software_renderer;
+
+use imgui::{sys, CapturedTexture, FrameCapture, ImDrawVert, ImVec2, OwnedDrawData,
+            OwnedDrawList, TextureId};
+use imgui_software_renderer::{Renderer, RgbaImage};
+use imgui_software_renderer::snapshot::{Snapshot, DEFAULT_CLEAR_COLOR};
+
+const FONT_TEXTURE: usize = 1;
+
+/// A 4x4 checkerboard of white and transparent texels.
+fn font_texture() -> CapturedTexture {
+    let mut pixels = Vec::new();
+    for y in 0..4 {
+        for x in 0..4 {
+            let value = if (x + y) % 2 == 0 { 255 } else { 0 };
+            pixels.extend_from_slice(&[255, 255, 255, value]);
+        }
+    }
+    CapturedTexture {
+        id: TextureId::new(FONT_TEXTURE),
+        width: 4,
+        height: 4,
+        pixels: pixels,
+    }
+}
+
+fn vertex(x: f32, y: f32, u: f32, v: f32, col: u32) -> ImDrawVert {
+    ImDrawVert {
+        pos: ImVec2::new(x, y),
+        uv: ImVec2::new(u, v),
+        col: col,
+    }
+}
+
+fn elements(count: u32, clip_rect: [f32; 4]) -> sys::ImDrawCmd {
+    sys::ImDrawCmd {
+        elem_count: count,
+        clip_rect: clip_rect.into(),
+        texture_id: FONT_TEXTURE as *mut _,
+        user_callback: None,
+        user_callback_data: ::std::ptr::null_mut(),
+    }
+}
+
+/// Appends a quad sampling the given uv rectangle with one vertex color.
+fn push_quad(list: &mut OwnedDrawList, rect: [f32; 4], uv: [f32; 4], col: u32) {
+    let base = list.vtx_buffer.len() as u16;
+    list.vtx_buffer.extend_from_slice(&[
+        vertex(rect[0], rect[1], uv[0], uv[1], col),
+        vertex(rect[2], rect[1], uv[2], uv[1], col),
+        vertex(rect[2], rect[3], uv[2], uv[3], col),
+        vertex(rect[0], rect[3], uv[0], uv[3], col),
+    ]);
+    list.idx_buffer
+        .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+}
+
+/// A frame like the ones imgui renders: solid shapes sample an opaque texel of the font
+/// texture, and the checkerboard stands in for text.
+fn capture() -> FrameCapture {
+    let solid = [0.0, 0.0, 0.0, 0.0];
+    let full_clip = [0.0, 0.0, 64.0, 48.0];
+    let mut window = OwnedDrawList {
+        cmd_buffer: Vec::new(),
+        idx_buffer: Vec::new(),
+        vtx_buffer: Vec::new(),
+    };
+    // Window background and title bar
+    push_quad(&mut window, [4.0, 4.0, 44.0, 36.0], solid, 0xf0_20_20_20);
+    push_quad(&mut window, [4.0, 4.0, 44.0, 10.0], solid, 0xff_8a_4a_29);
+    // "Text", magnified so that every texel covers 2x2 pixels of the display
+    push_quad(&mut window, [8.0, 14.0, 16.0, 22.0], [0.0, 0.0, 1.0, 1.0], 0xff_ff_ff_ff);
+    // A triangle with a vertex color gradient
+    let base = window.vtx_buffer.len() as u16;
+    window.vtx_buffer.extend_from_slice(&[
+        vertex(22.0, 30.0, 0.0, 0.0, 0xff_00_00_ff),
+        vertex(30.0, 14.0, 0.0, 0.0, 0xff_00_ff_00),
+        vertex(38.0, 30.0, 0.0, 0.0, 0xff_ff_00_00),
+    ]);
+    window.idx_buffer.extend_from_slice(&[base, base + 1, base + 2]);
+    window.cmd_buffer.push(elements(21, full_clip));
+    // A button clipped to the window
+    push_quad(&mut window, [34.0, 30.0, 52.0, 40.0], solid, 0xff_fa_96_42);
+    window.cmd_buffer.push(elements(6, [4.0, 4.0, 44.0, 36.0]));
+
+    // A translucent popup over the window
+    let mut popup = OwnedDrawList {
+        cmd_buffer: Vec::new(),
+        idx_buffer: Vec::new(),
+        vtx_buffer: Vec::new(),
+    };
+    push_quad(&mut popup, [36.0, 20.0, 60.0, 44.0], solid, 0x80_ff_ff_ff);
+    popup.cmd_buffer.push(elements(6, full_clip));
+
+    let mut draw_data = OwnedDrawData::new((64.0, 48.0));
+    draw_data.draw_lists = vec![window, popup];
+    FrameCapture {
+        draw_data: draw_data,
+        font_texture: Some(font_texture()),
+    }
+}
+
+#[test]
+fn captured_frame() {
+    // Round tripping through the capture format keeps the frame intact
+    let mut data = Vec::new();
+    capture().write_to(&mut data).unwrap();
+    let mut capture = FrameCapture::read_from(&mut &data[..]).unwrap();
+
+    let mut renderer = Renderer::new();
+    let mut image = RgbaImage::filled(128, 96, DEFAULT_CLEAR_COLOR);
+    renderer.render_capture(&mut image, &mut capture).unwrap();
+    Snapshot::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/snapshots/captured_frame.png"
+    )).assert_matches(&image);
+}
@@ -326,6 +326,22 @@ bitflags!(
     }
 );
 
+bitflags!(
+    /// Flags for which corners of a rectangle are rounded
+    #[repr(C)]
+    pub struct ImDrawCornerFlags: c_int {
+        const TopLeft  = 1;
+        const TopRight = 1 << 1;
+        const BotRight = 1 << 2;
+        const BotLeft  = 1 << 3;
+        const Top = ImDrawCornerFlags::TopLeft.bits | ImDrawCornerFlags::TopRight.bits;
+        const Bot = ImDrawCornerFlags::BotLeft.bits | ImDrawCornerFlags::BotRight.bits;
+        const Left = ImDrawCornerFlags::TopLeft.bits | ImDrawCornerFlags::BotLeft.bits;
+        const Right = ImDrawCornerFlags::TopRight.bits | ImDrawCornerFlags::BotRight.bits;
+        const All = 0xF;
+    }
+);
+
 pub type ImGuiTextEditCallback = Option<
     extern "C" fn(data: *mut ImGuiTextEditCallbackData) -> c_int,
 >;
@@ -595,6 +611,16 @@ impl<T> ImVector<T> {
     pub unsafe fn as_slice(&self) -> &[T] { slice::from_raw_parts(self.data, self.size as usize) }
 }
 
+impl<T> Default for ImVector<T> {
+    fn default() -> ImVector<T> {
+        ImVector {
+            size: 0,
+            capacity: 0,
+            data: ptr::null_mut(),
+        }
+    }
+}
+
 #[repr(C)]
 pub struct TextRange {
     pub begin: *const c_char,
@@ -664,6 +690,44 @@ pub struct ImColor {
     pub value: ImVec4,
 }
 
+impl ImColor {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> ImColor {
+        ImColor { value: ImVec4::new(r, g, b, a) }
+    }
+}
+
+impl From<ImVec4> for ImColor {
+    fn from(value: ImVec4) -> ImColor { ImColor { value: value } }
+}
+
+impl From<[f32; 4]> for ImColor {
+    fn from(array: [f32; 4]) -> ImColor { ImColor::new(array[0], array[1], array[2], array[3]) }
+}
+
+impl From<(f32, f32, f32, f32)> for ImColor {
+    fn from((r, g, b, a): (f32, f32, f32, f32)) -> ImColor { ImColor::new(r, g, b, a) }
+}
+
+impl From<[f32; 3]> for ImColor {
+    fn from(array: [f32; 3]) -> ImColor { ImColor::new(array[0], array[1], array[2], 1.0) }
+}
+
+impl From<(f32, f32, f32)> for ImColor {
+    fn from((r, g, b): (f32, f32, f32)) -> ImColor { ImColor::new(r, g, b, 1.0) }
+}
+
+impl From<ImU32> for ImColor {
+    fn from(color: ImU32) -> ImColor {
+        let mut value = ImVec4::zero();
+        unsafe { igColorConvertU32ToFloat4(&mut value, color) };
+        ImColor { value: value }
+    }
+}
+
+impl From<ImColor> for ImU32 {
+    fn from(color: ImColor) -> ImU32 { unsafe { igColorConvertFloat4ToU32(color.value) } }
+}
+
 /// Helper to manually clip large list of items
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
@@ -724,8 +788,8 @@ pub struct ImDrawList {
     clip_rect_stack: ImVector<ImVec4>,
     texture_id_stack: ImVector<ImTextureID>,
     path: ImVector<ImVec2>,
-    channels_current: c_int,
-    channels_count: c_int,
+    pub channels_current: c_int,
+    pub channels_count: c_int,
     channels: ImVector<ImDrawChannel>,
 }
 
@@ -768,6 +832,30 @@ pub struct ImFontConfig {
     dst_font: *mut ImFont,
 }
 
+impl Default for ImFontConfig {
+    /// Equivalent to the C++ ImFontConfig constructor.
+    fn default() -> ImFontConfig {
+        ImFontConfig {
+            font_data: ptr::null_mut(),
+            font_data_size: 0,
+            font_data_owned_by_atlas: true,
+            font_no: 0,
+            size_pixels: 0.0,
+            oversample_h: 3,
+            oversample_v: 1,
+            pixel_snap_h: false,
+            glyph_extra_spacing: ImVec2::zero(),
+            glyph_offset: ImVec2::zero(),
+            glyph_ranges: ptr::null(),
+            merge_mode: false,
+            rasterizer_flags: 0,
+            rasterizer_multiply: 1.0,
+            name: [0; 32],
+            dst_font: ptr::null_mut(),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Default)]
 pub struct ImFontGlyph {
@@ -813,6 +901,29 @@ pub struct ImFontAtlas {
     custom_rect_ids: [c_int; 1],
 }
 
+impl Default for ImFontAtlas {
+    /// Equivalent to the C++ ImFontAtlas constructor.
+    ///
+    /// The atlas allocates memory through the current context, so it must be cleared with
+    /// `ImFontAtlas_Clear` before being dropped.
+    fn default() -> ImFontAtlas {
+        ImFontAtlas {
+            tex_id: ptr::null_mut(),
+            tex_desired_width: 0,
+            tex_glyph_padding: 1,
+            tex_pixels_alpha8: ptr::null_mut(),
+            tex_pixels_rgba32: ptr::null_mut(),
+            tex_width: 0,
+            tex_height: 0,
+            tex_uv_white_pixel: ImVec2::zero(),
+            fonts: ImVector::default(),
+            custom_rects: ImVector::default(),
+            config_data: ImVector::default(),
+            custom_rect_ids: [-1],
+        }
+    }
+}
+
 /// Runtime data for a single font within a parent ImFontAtlas
 #[repr(C)]
 pub struct ImFont {
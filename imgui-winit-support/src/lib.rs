@@ -0,0 +1,407 @@
+//! Platform integration for using imgui with [winit](https://github.com/tomaka/winit) windows
+//! created through glutin.
+//!
+//! `WinitPlatform` turns window events into imgui input, converts between window and imgui
+//! coordinates according to a `HiDpiMode`, and prepares the display size and delta time of each
+//! frame.
+//!
+//! ```rust,no_run
+//! # extern crate glutin;
+//! # extern crate imgui;
+//! # extern crate imgui_winit_support;
+//! # use imgui::ImGui;
+//! # use imgui_winit_support::{HiDpiMode, WinitPlatform};
+//! # fn main() {
+//! # let mut events_loop = glutin::EventsLoop::new();
+//! # let window = glutin::WindowBuilder::new().build(&events_loop).unwrap();
+//! let mut imgui = ImGui::init();
+//! let mut platform = WinitPlatform::init();
+//! platform.attach_window(&window, HiDpiMode::Default);
+//!
+//! loop {
+//!     events_loop.poll_events(|event| platform.handle_event(&mut imgui, &event));
+//!
+//!     let ui = platform.frame(&mut imgui, &window);
+//!     // build the ui...
+//!     platform.prepare_render(&ui, &window);
+//!     // render...
+//! #   break;
+//! }
+//! # }
+//! ```
+extern crate glutin;
+extern crate imgui;
+
+use glutin::dpi::{LogicalPosition, LogicalSize};
+use glutin::{ElementState, Event, MouseScrollDelta, TouchPhase, VirtualKeyCode, Window,
+             WindowEvent};
+use imgui::{ImGui, InputEvent, Key, MouseButton, MouseCursor, Ui};
+use std::cell::Cell;
+use std::f32;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// How the hidpi factor of the window is used for imgui.
+///
+/// imgui works in points, and the hidpi factor is the number of framebuffer pixels per point.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HiDpiMode {
+    /// Uses the hidpi factor of the window as is. Points are the same as winit's logical
+    /// coordinates.
+    Default,
+    /// Rounds the hidpi factor of the window to the nearest integer, which keeps fonts and
+    /// lines sharp with fractional factors such as 1.5. Points are then slightly smaller or
+    /// larger than winit's logical coordinates.
+    Rounded,
+    /// Always uses the given hidpi factor and ignores the factor of the window.
+    Locked(f64),
+}
+
+impl HiDpiMode {
+    /// Returns the hidpi factor used for imgui when the window has the given factor.
+    pub fn apply(self, window_hidpi_factor: f64) -> f64 {
+        match self {
+            HiDpiMode::Default => window_hidpi_factor,
+            HiDpiMode::Rounded => window_hidpi_factor.round().max(1.0),
+            HiDpiMode::Locked(factor) => factor,
+        }
+    }
+}
+
+/// Connects an `ImGui` instance to a winit window.
+pub struct WinitPlatform {
+    hidpi_mode: HiDpiMode,
+    window_hidpi_factor: f64,
+    last_frame: Instant,
+    last_cursor: Option<Option<MouseCursor>>,
+    ime_position: Rc<Cell<Option<(f32, f32)>>>,
+}
+
+impl WinitPlatform {
+    /// Creates a platform. The hidpi factor is 1.0 until a window is attached.
+    pub fn init() -> WinitPlatform {
+        WinitPlatform {
+            hidpi_mode: HiDpiMode::Default,
+            window_hidpi_factor: 1.0,
+            last_frame: Instant::now(),
+            last_cursor: None,
+            ime_position: Rc::new(Cell::new(None)),
+        }
+    }
+    /// Starts using the given window and hidpi mode.
+    pub fn attach_window(&mut self, window: &Window, hidpi_mode: HiDpiMode) {
+        self.hidpi_mode = hidpi_mode;
+        self.window_hidpi_factor = window.get_hidpi_factor();
+        self.last_cursor = None;
+    }
+    /// Makes the IME candidate window follow the text cursor of the active text input.
+    ///
+    /// This replaces any IME position handler of the context.
+    pub fn attach_ime(&mut self, imgui: &mut ImGui) {
+        let ime_position = self.ime_position.clone();
+        imgui.set_ime_position_handler(move |x, y| ime_position.set(Some((x, y))));
+    }
+    /// Returns the hidpi mode.
+    pub fn hidpi_mode(&self) -> HiDpiMode { self.hidpi_mode }
+    /// Changes the hidpi mode. Fonts depending on `hidpi_factor` need to be rebuilt afterwards.
+    pub fn set_hidpi_mode(&mut self, hidpi_mode: HiDpiMode) { self.hidpi_mode = hidpi_mode; }
+    /// Returns the hidpi factor of the window, as last reported by winit.
+    pub fn window_hidpi_factor(&self) -> f64 { self.window_hidpi_factor }
+    /// Returns the hidpi factor used for imgui, i.e. the number of framebuffer pixels per
+    /// point. Applications usually scale their font sizes by this.
+    ///
+    /// The factor can change while handling events, in which case fonts need to be rebuilt.
+    pub fn hidpi_factor(&self) -> f64 { self.hidpi_mode.apply(self.window_hidpi_factor) }
+    /// Converts a logical position reported by winit to imgui points.
+    pub fn scale_pos_from_winit(&self, pos: LogicalPosition) -> (f32, f32) {
+        let scale = self.window_hidpi_factor / self.hidpi_factor();
+        ((pos.x * scale) as f32, (pos.y * scale) as f32)
+    }
+    /// Converts imgui points to a logical position for winit.
+    pub fn scale_pos_for_winit(&self, pos: (f32, f32)) -> LogicalPosition {
+        let scale = self.hidpi_factor() / self.window_hidpi_factor;
+        LogicalPosition::new(pos.0 as f64 * scale, pos.1 as f64 * scale)
+    }
+    /// Converts a logical size reported by winit to imgui points.
+    pub fn scale_size_from_winit(&self, size: LogicalSize) -> (f32, f32) {
+        let scale = self.window_hidpi_factor / self.hidpi_factor();
+        ((size.width * scale) as f32, (size.height * scale) as f32)
+    }
+    /// Handles an event of any window. Events of other windows should be filtered out by the
+    /// caller when using several windows.
+    pub fn handle_event(&mut self, imgui: &mut ImGui, event: &Event) {
+        if let Event::WindowEvent { ref event, .. } = *event {
+            self.handle_window_event(imgui, event);
+        }
+    }
+    /// Handles a window event by queueing the corresponding imgui input events.
+    pub fn handle_window_event(&mut self, imgui: &mut ImGui, event: &WindowEvent) {
+        match *event {
+            WindowEvent::HiDpiFactorChanged(factor) => self.window_hidpi_factor = factor,
+            WindowEvent::Focused(focused) => imgui.push_event(InputEvent::Focus(focused)),
+            WindowEvent::KeyboardInput { input, .. } => {
+                let pressed = input.state == ElementState::Pressed;
+                if let Some(key) = input.virtual_keycode.and_then(map_key) {
+                    imgui.push_event(InputEvent::Key(key, pressed));
+                }
+            }
+            WindowEvent::ReceivedCharacter(c) => {
+                // Control characters are handled as keys
+                if !c.is_control() {
+                    imgui.push_event(InputEvent::Char(c));
+                }
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let (x, y) = self.scale_pos_from_winit(position);
+                imgui.push_event(InputEvent::MousePos(x, y));
+            }
+            WindowEvent::CursorLeft { .. } => {
+                imgui.push_event(InputEvent::MousePos(-f32::MAX, -f32::MAX))
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                if let Some(button) = map_mouse_button(button) {
+                    let pressed = state == ElementState::Pressed;
+                    imgui.push_event(InputEvent::MouseButton(button, pressed));
+                }
+            }
+            WindowEvent::MouseWheel { delta, phase: TouchPhase::Moved, .. } => {
+                let lines = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => y,
+                    // Roughly one line per font height
+                    MouseScrollDelta::PixelDelta(pos) => self.scale_pos_from_winit(pos).1 / 13.0,
+                };
+                imgui.push_event(InputEvent::MouseWheel(lines));
+            }
+            _ => (),
+        }
+    }
+    /// Returns the display size in points and in framebuffer pixels for `ImGui::frame`.
+    ///
+    /// Returns `None` if the window no longer exists.
+    pub fn frame_size(&self, window: &Window) -> Option<((u32, u32), (u32, u32))> {
+        window.get_inner_size().map(|size| {
+            let physical = size.to_physical(self.window_hidpi_factor);
+            let hidpi_factor = self.hidpi_factor();
+            let points = (
+                (physical.width / hidpi_factor).round() as u32,
+                (physical.height / hidpi_factor).round() as u32,
+            );
+            (points, physical.into())
+        })
+    }
+    /// Returns the time since the previous call in seconds, for `ImGui::frame`.
+    pub fn update_delta_time(&mut self) -> f32 {
+        let now = Instant::now();
+        let delta = now - self.last_frame;
+        self.last_frame = now;
+        delta.as_secs() as f32 + delta.subsec_nanos() as f32 / 1_000_000_000.0
+    }
+    /// Starts a new frame with the current display size and delta time.
+    pub fn frame<'ui, 'a: 'ui>(&mut self, imgui: &'a mut ImGui, window: &Window) -> Ui<'ui> {
+        let (size_points, size_pixels) = self.frame_size(window).unwrap_or(((0, 0), (0, 0)));
+        let delta_time = self.update_delta_time();
+        imgui.frame(size_points, size_pixels, delta_time)
+    }
+    /// Updates the window after building a frame. This should be called before rendering.
+    ///
+    /// The mouse cursor of the window is changed to the cursor imgui wants, and hidden if imgui
+    /// draws the cursor itself. If `attach_ime` has been called, the IME candidate window is
+    /// moved to the text cursor position reported in the previous frame.
+    pub fn prepare_render(&mut self, ui: &Ui, window: &Window) {
+        if let Some(pos) = self.ime_position.take() {
+            window.set_ime_spot(self.scale_pos_for_winit(pos));
+        }
+        let cursor = if ui.imgui().mouse_draw_cursor() {
+            None
+        } else {
+            ui.mouse_cursor()
+        };
+        if self.last_cursor != Some(cursor) {
+            self.last_cursor = Some(cursor);
+            match cursor {
+                Some(cursor) => {
+                    window.hide_cursor(false);
+                    window.set_cursor(map_cursor(cursor));
+                }
+                None => window.hide_cursor(true),
+            }
+        }
+    }
+}
+
+/// Maps a winit key to an imgui key.
+pub fn map_key(key: VirtualKeyCode) -> Option<Key> {
+    use glutin::VirtualKeyCode as K;
+
+    Some(match key {
+        K::Tab => Key::Tab,
+        K::Left => Key::LeftArrow,
+        K::Right => Key::RightArrow,
+        K::Up => Key::UpArrow,
+        K::Down => Key::DownArrow,
+        K::PageUp => Key::PageUp,
+        K::PageDown => Key::PageDown,
+        K::Home => Key::Home,
+        K::End => Key::End,
+        K::Insert => Key::Insert,
+        K::Delete => Key::Delete,
+        K::Back => Key::Backspace,
+        K::Space => Key::Space,
+        K::Return => Key::Enter,
+        K::Escape => Key::Escape,
+        K::LControl => Key::LeftCtrl,
+        K::LShift => Key::LeftShift,
+        K::LAlt => Key::LeftAlt,
+        K::LWin => Key::LeftSuper,
+        K::RControl => Key::RightCtrl,
+        K::RShift => Key::RightShift,
+        K::RAlt => Key::RightAlt,
+        K::RWin => Key::RightSuper,
+        K::Apps => Key::Menu,
+        K::Key0 => Key::Alpha0,
+        K::Key1 => Key::Alpha1,
+        K::Key2 => Key::Alpha2,
+        K::Key3 => Key::Alpha3,
+        K::Key4 => Key::Alpha4,
+        K::Key5 => Key::Alpha5,
+        K::Key6 => Key::Alpha6,
+        K::Key7 => Key::Alpha7,
+        K::Key8 => Key::Alpha8,
+        K::Key9 => Key::Alpha9,
+        K::A => Key::A,
+        K::B => Key::B,
+        K::C => Key::C,
+        K::D => Key::D,
+        K::E => Key::E,
+        K::F => Key::F,
+        K::G => Key::G,
+        K::H => Key::H,
+        K::I => Key::I,
+        K::J => Key::J,
+        K::K => Key::K,
+        K::L => Key::L,
+        K::M => Key::M,
+        K::N => Key::N,
+        K::O => Key::O,
+        K::P => Key::P,
+        K::Q => Key::Q,
+        K::R => Key::R,
+        K::S => Key::S,
+        K::T => Key::T,
+        K::U => Key::U,
+        K::V => Key::V,
+        K::W => Key::W,
+        K::X => Key::X,
+        K::Y => Key::Y,
+        K::Z => Key::Z,
+        K::F1 => Key::F1,
+        K::F2 => Key::F2,
+        K::F3 => Key::F3,
+        K::F4 => Key::F4,
+        K::F5 => Key::F5,
+        K::F6 => Key::F6,
+        K::F7 => Key::F7,
+        K::F8 => Key::F8,
+        K::F9 => Key::F9,
+        K::F10 => Key::F10,
+        K::F11 => Key::F11,
+        K::F12 => Key::F12,
+        K::Apostrophe => Key::Apostrophe,
+        K::Comma => Key::Comma,
+        K::Minus => Key::Minus,
+        K::Period => Key::Period,
+        K::Slash => Key::Slash,
+        K::Semicolon => Key::Semicolon,
+        K::Equals => Key::Equal,
+        K::LBracket => Key::LeftBracket,
+        K::Backslash => Key::Backslash,
+        K::RBracket => Key::RightBracket,
+        K::Grave => Key::GraveAccent,
+        K::Capital => Key::CapsLock,
+        K::Scroll => Key::ScrollLock,
+        K::Numlock => Key::NumLock,
+        K::Snapshot => Key::PrintScreen,
+        K::Pause => Key::Pause,
+        K::Numpad0 => Key::Keypad0,
+        K::Numpad1 => Key::Keypad1,
+        K::Numpad2 => Key::Keypad2,
+        K::Numpad3 => Key::Keypad3,
+        K::Numpad4 => Key::Keypad4,
+        K::Numpad5 => Key::Keypad5,
+        K::Numpad6 => Key::Keypad6,
+        K::Numpad7 => Key::Keypad7,
+        K::Numpad8 => Key::Keypad8,
+        K::Numpad9 => Key::Keypad9,
+        K::Decimal => Key::KeypadDecimal,
+        K::Divide => Key::KeypadDivide,
+        K::Multiply => Key::KeypadMultiply,
+        K::Subtract => Key::KeypadSubtract,
+        K::Add => Key::KeypadAdd,
+        K::NumpadEnter => Key::KeypadEnter,
+        K::NumpadEquals => Key::KeypadEqual,
+        _ => return None,
+    })
+}
+
+/// Maps a winit mouse button to an imgui mouse button.
+pub fn map_mouse_button(button: glutin::MouseButton) -> Option<MouseButton> {
+    match button {
+        glutin::MouseButton::Left => Some(MouseButton::Left),
+        glutin::MouseButton::Right => Some(MouseButton::Right),
+        glutin::MouseButton::Middle => Some(MouseButton::Middle),
+        glutin::MouseButton::Other(0) => Some(MouseButton::Extra1),
+        glutin::MouseButton::Other(1) => Some(MouseButton::Extra2),
+        glutin::MouseButton::Other(_) => None,
+    }
+}
+
+/// Maps an imgui mouse cursor to a winit mouse cursor.
+pub fn map_cursor(cursor: MouseCursor) -> glutin::MouseCursor {
+    match cursor {
+        MouseCursor::Arrow => glutin::MouseCursor::Default,
+        MouseCursor::TextInput => glutin::MouseCursor::Text,
+        MouseCursor::Move => glutin::MouseCursor::Move,
+        MouseCursor::ResizeNS => glutin::MouseCursor::NsResize,
+        MouseCursor::ResizeEW => glutin::MouseCursor::EwResize,
+        MouseCursor::ResizeNESW => glutin::MouseCursor::NeswResize,
+        MouseCursor::ResizeNWSE => glutin::MouseCursor::NwseResize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidpi_modes() {
+        assert_eq!(HiDpiMode::Default.apply(1.5), 1.5);
+        assert_eq!(HiDpiMode::Rounded.apply(1.5), 2.0);
+        assert_eq!(HiDpiMode::Rounded.apply(0.75), 1.0);
+        assert_eq!(HiDpiMode::Locked(1.0).apply(2.0), 1.0);
+    }
+
+    #[test]
+    fn hidpi_factor_changes() {
+        let mut imgui = ImGui::init();
+        let mut platform = WinitPlatform::init();
+        platform.set_hidpi_mode(HiDpiMode::Rounded);
+        platform.handle_window_event(&mut imgui, &WindowEvent::HiDpiFactorChanged(1.5));
+        assert_eq!(platform.window_hidpi_factor(), 1.5);
+        assert_eq!(platform.hidpi_factor(), 2.0);
+        // 100 logical units are 150 pixels, which are 75 points
+        let pos = platform.scale_pos_from_winit(LogicalPosition::new(100.0, 200.0));
+        assert_eq!(pos, (75.0, 150.0));
+        let size = platform.scale_size_from_winit(LogicalSize::new(100.0, 200.0));
+        assert_eq!(size, (75.0, 150.0));
+    }
+
+    #[test]
+    fn characters_are_queued() {
+        let mut imgui = ImGui::init();
+        let mut platform = WinitPlatform::init();
+        platform.handle_window_event(&mut imgui, &WindowEvent::ReceivedCharacter('a'));
+        platform.handle_window_event(&mut imgui, &WindowEvent::ReceivedCharacter('\r'));
+        platform.handle_window_event(&mut imgui, &WindowEvent::Focused(false));
+        assert_eq!(imgui.queued_event_count(), 2);
+    }
+}
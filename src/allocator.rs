@@ -0,0 +1,150 @@
+use std::mem;
+use std::os::raw::c_void;
+// AtomicUsize::new can't be used in statics before Rust 1.24
+#[allow(deprecated)]
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+use sys;
+
+const UNDECIDED: usize = 0;
+const SYSTEM: usize = 1;
+const RUST: usize = 2;
+
+/// Which allocator dear imgui uses. It's decided when the first context is created.
+#[allow(deprecated)]
+static MODE: AtomicUsize = ATOMIC_USIZE_INIT;
+
+#[allow(deprecated)]
+static LIVE_ALLOCATIONS: AtomicUsize = ATOMIC_USIZE_INIT;
+#[allow(deprecated)]
+static LIVE_BYTES: AtomicUsize = ATOMIC_USIZE_INIT;
+#[allow(deprecated)]
+static TOTAL_ALLOCATIONS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// Allocation counts of dear imgui when it uses the Rust allocator.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocatorStats {
+    /// Number of allocations that haven't been freed yet
+    pub live_allocations: usize,
+    /// Total size in bytes of the allocations that haven't been freed yet, with each size
+    /// rounded up to a multiple of 8 and excluding headers
+    pub live_bytes: usize,
+    /// Number of allocations made since the Rust allocator was enabled
+    pub total_allocations: usize,
+}
+
+/// Routes all dear imgui allocations through the Rust global allocator, so they are visible to
+/// custom or tracking allocators and counted in `allocator_stats`.
+///
+/// Every context must use the same allocator because memory such as the font atlas can be
+/// freed through a different context than the one that allocated it. This therefore has to be
+/// called before the first `ImGui` is created, and returns false without changing anything if
+/// imgui has already started using the system allocator.
+pub fn enable_rust_allocator() -> bool {
+    match MODE.compare_exchange(UNDECIDED, RUST, Ordering::SeqCst, Ordering::SeqCst) {
+        Ok(_) => true,
+        Err(mode) => mode == RUST,
+    }
+}
+
+/// Returns true if dear imgui allocates through the Rust global allocator.
+pub fn is_rust_allocator_enabled() -> bool { MODE.load(Ordering::SeqCst) == RUST }
+
+/// Returns the allocation counts of dear imgui, or `None` if it doesn't use the Rust allocator.
+pub fn allocator_stats() -> Option<AllocatorStats> {
+    if is_rust_allocator_enabled() {
+        Some(AllocatorStats {
+            live_allocations: LIVE_ALLOCATIONS.load(Ordering::SeqCst),
+            live_bytes: LIVE_BYTES.load(Ordering::SeqCst),
+            total_allocations: TOTAL_ALLOCATIONS.load(Ordering::SeqCst),
+        })
+    } else {
+        None
+    }
+}
+
+/// Creates a dear imgui context with the chosen allocator, locking in the system allocator if
+/// `enable_rust_allocator` hasn't been called.
+pub(crate) fn create_context() -> *mut sys::ImGuiContext {
+    let _ = MODE.compare_exchange(UNDECIDED, SYSTEM, Ordering::SeqCst, Ordering::SeqCst);
+    if is_rust_allocator_enabled() {
+        unsafe { sys::igCreateContext(Some(rust_alloc), Some(rust_free)) }
+    } else {
+        unsafe { sys::igCreateContext(None, None) }
+    }
+}
+
+// Allocations are made as Vec<u64> buffers, which are only aligned to 8 bytes, while dear
+// imgui expects the 16-byte alignment of malloc on 64-bit platforms. Rust 1.20 has no way to
+// ask for more, so each buffer has room for one extra word and the returned pointer is rounded
+// up to a multiple of 16. The two words before it store the capacity of the buffer and the
+// offset of the pointer from the start of the buffer in words, so the buffer can be rebuilt
+// when it's freed.
+type Word = u64;
+const WORD_SIZE: usize = mem::size_of::<Word>();
+const ALIGN: usize = 16;
+const HEADER_WORDS: usize = 2;
+const PADDING_WORDS: usize = ALIGN / WORD_SIZE - 1;
+
+/// Returns the size of the data in a buffer of the given capacity, excluding the header and
+/// the padding.
+fn data_bytes(capacity: usize) -> usize { (capacity - HEADER_WORDS - PADDING_WORDS) * WORD_SIZE }
+
+extern "C" fn rust_alloc(size: usize) -> *mut c_void {
+    let data_words = (size + WORD_SIZE - 1) / WORD_SIZE;
+    let mut buf: Vec<Word> = Vec::with_capacity(HEADER_WORDS + PADDING_WORDS + data_words);
+    let capacity = buf.capacity();
+    let base = buf.as_mut_ptr();
+    mem::forget(buf);
+    let start = base as usize + HEADER_WORDS * WORD_SIZE;
+    let offset = ((start + ALIGN - 1) / ALIGN * ALIGN - base as usize) / WORD_SIZE;
+    LIVE_ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+    LIVE_BYTES.fetch_add(data_bytes(capacity), Ordering::SeqCst);
+    TOTAL_ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+    unsafe {
+        let ptr = base.offset(offset as isize);
+        *ptr.offset(-2) = capacity as Word;
+        *ptr.offset(-1) = offset as Word;
+        ptr as *mut c_void
+    }
+}
+
+extern "C" fn rust_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let ptr = ptr as *mut Word;
+        let capacity = *ptr.offset(-2) as usize;
+        let offset = *ptr.offset(-1) as isize;
+        LIVE_ALLOCATIONS.fetch_sub(1, Ordering::SeqCst);
+        LIVE_BYTES.fetch_sub(data_bytes(capacity), Ordering::SeqCst);
+        drop(Vec::from_raw_parts(ptr.offset(-offset), 0, capacity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+
+    #[test]
+    fn aligned_allocations() {
+        let live_allocations = LIVE_ALLOCATIONS.load(Ordering::SeqCst);
+        let mut allocations = Vec::new();
+        for size in 0..64 {
+            let ptr = rust_alloc(size);
+            assert_eq!(ptr as usize % ALIGN, 0, "allocation of {} bytes", size);
+            // The whole allocation is usable
+            unsafe { ptr::write_bytes(ptr as *mut u8, 0xab, size) };
+            allocations.push(ptr);
+        }
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), live_allocations + 64);
+        for ptr in allocations {
+            rust_free(ptr);
+        }
+        rust_free(ptr::null_mut());
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), live_allocations);
+    }
+}
@@ -0,0 +1,76 @@
+//! Little-endian helpers for the binary formats of `InputRecording` and `FrameCapture`.
+use std::io::{self, Read, Write};
+
+pub(crate) fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Replaces an `UnexpectedEof` error, i.e. input that ended in the middle of a value, with an
+/// `InvalidData` error with the given message. Other errors are returned unchanged.
+pub(crate) fn truncated(error: io::Error, msg: &str) -> io::Error {
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        invalid_data(msg)
+    } else {
+        error
+    }
+}
+
+pub(crate) fn write_u8<W: Write>(w: &mut W, value: u8) -> io::Result<()> { w.write_all(&[value]) }
+
+pub(crate) fn write_u16<W: Write>(w: &mut W, value: u16) -> io::Result<()> {
+    w.write_all(&[value as u8, (value >> 8) as u8])
+}
+
+pub(crate) fn write_u32<W: Write>(w: &mut W, value: u32) -> io::Result<()> {
+    w.write_all(&[
+        value as u8,
+        (value >> 8) as u8,
+        (value >> 16) as u8,
+        (value >> 24) as u8,
+    ])
+}
+
+pub(crate) fn write_u64<W: Write>(w: &mut W, value: u64) -> io::Result<()> {
+    write_u32(w, value as u32)?;
+    write_u32(w, (value >> 32) as u32)
+}
+
+pub(crate) fn write_f32<W: Write>(w: &mut W, value: f32) -> io::Result<()> {
+    write_u32(w, value.to_bits())
+}
+
+pub(crate) fn read_u8<R: Read>(r: &mut R) -> io::Result<u8> {
+    let mut buf = [0];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+pub(crate) fn read_bool<R: Read>(r: &mut R) -> io::Result<bool> {
+    match read_u8(r)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(invalid_data("Invalid boolean")),
+    }
+}
+
+pub(crate) fn read_u16<R: Read>(r: &mut R) -> io::Result<u16> {
+    let mut buf = [0; 2];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0] as u16 | (buf[1] as u16) << 8)
+}
+
+pub(crate) fn read_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    r.read_exact(&mut buf)?;
+    Ok(
+        buf[0] as u32 | (buf[1] as u32) << 8 | (buf[2] as u32) << 16 | (buf[3] as u32) << 24,
+    )
+}
+
+pub(crate) fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let low = read_u32(r)? as u64;
+    let high = read_u32(r)? as u64;
+    Ok(low | high << 32)
+}
+
+pub(crate) fn read_f32<R: Read>(r: &mut R) -> io::Result<f32> { read_u32(r).map(f32::from_bits) }
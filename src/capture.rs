@@ -0,0 +1,392 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use binary::{invalid_data, read_f32, read_u16, read_u32, read_u64, read_u8, truncated, write_f32,
+             write_u16, write_u32, write_u64, write_u8};
+use render::{reset_render_state_cmd, OwnedDrawList};
+use sys;
+use super::{DrawData, ImDrawVert, ImVec2, OwnedDrawData, TextureId};
+
+const MAGIC: &'static [u8; 8] = b"IMGUIDRW";
+const VERSION: u32 = 1;
+
+/// The largest font texture width and height that is read
+const MAX_TEXTURE_SIZE: u32 = 16384;
+
+const CMD_ELEMENTS: u8 = 0;
+const CMD_RESET_RENDER_STATE: u8 = 1;
+
+/// The font atlas texture of a `FrameCapture`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedTexture {
+    /// The texture id used by the draw commands that draw with the font atlas
+    pub id: TextureId,
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels in row-major order
+    pub pixels: Vec<u8>,
+}
+
+/// A rendered frame that can be saved to a file and loaded back, e.g. for attaching a UI to a
+/// bug report, inspecting draw call counts offline or rendering it again for regression tests.
+///
+/// Captures are stored in a versioned little-endian binary format, containing the display size,
+/// the font atlas pixels and every draw list with its vertices, indices and draw commands.
+/// Application textures are referenced only by their ids.
+pub struct FrameCapture {
+    pub draw_data: OwnedDrawData,
+    pub font_texture: Option<CapturedTexture>,
+}
+
+impl FrameCapture {
+    /// Captures the draw data of a rendered frame and the font atlas of its context.
+    ///
+    /// Draw data that isn't rendered by a context, such as the draw data of a loaded capture,
+    /// is captured without a font texture.
+    pub fn new(draw_data: &DrawData) -> FrameCapture {
+        let font_texture = draw_data.imgui().map(|imgui| {
+            let (width, height, pixels) = imgui.font_texture_rgba32();
+            CapturedTexture {
+                id: imgui.font_texture_id(),
+                width: width,
+                height: height,
+                pixels: pixels.to_vec(),
+            }
+        });
+        FrameCapture {
+            draw_data: OwnedDrawData::from(draw_data),
+            font_texture: font_texture,
+        }
+    }
+
+    /// Writes the capture in the binary capture format.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+        write_u32(w, VERSION)?;
+        write_f32(w, self.draw_data.display_size.0)?;
+        write_f32(w, self.draw_data.display_size.1)?;
+        match self.font_texture {
+            Some(ref texture) => {
+                write_u8(w, 1)?;
+                texture.write_to(w)?;
+            }
+            None => write_u8(w, 0)?,
+        }
+        write_u32(w, self.draw_data.draw_lists.len() as u32)?;
+        for list in &self.draw_data.draw_lists {
+            list.write_to(w)?;
+        }
+        Ok(())
+    }
+    /// Reads a capture in the binary capture format.
+    ///
+    /// Data that isn't a valid capture, including a truncated one, is reported as an
+    /// `InvalidData` error.
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<FrameCapture> {
+        FrameCapture::read_capture(r).map_err(|e| truncated(e, "Truncated frame capture"))
+    }
+    fn read_capture<R: Read>(r: &mut R) -> io::Result<FrameCapture> {
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("Not a frame capture"));
+        }
+        if read_u32(r)? != VERSION {
+            return Err(invalid_data("Unsupported frame capture version"));
+        }
+        let display_size = (read_f32(r)?, read_f32(r)?);
+        let font_texture = match read_u8(r)? {
+            0 => None,
+            1 => Some(CapturedTexture::read_from(r)?),
+            _ => return Err(invalid_data("Invalid font texture flag")),
+        };
+        let mut draw_data = OwnedDrawData::new(display_size);
+        for _ in 0..read_u32(r)? {
+            draw_data.draw_lists.push(OwnedDrawList::read_from(r)?);
+        }
+        Ok(FrameCapture {
+            draw_data: draw_data,
+            font_texture: font_texture,
+        })
+    }
+    /// Saves the capture to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write_to(&mut w)?;
+        w.flush()
+    }
+    /// Loads a capture from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<FrameCapture> {
+        FrameCapture::read_from(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+impl CapturedTexture {
+    /// Writes the texture in the format used by `FrameCapture`.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write_u64(w, self.id.id() as u64)?;
+        write_u32(w, self.width)?;
+        write_u32(w, self.height)?;
+        w.write_all(&self.pixels)
+    }
+    /// Reads a texture in the format used by `FrameCapture`.
+    ///
+    /// Textures larger than 16384x16384 pixels are rejected as invalid data.
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<CapturedTexture> {
+        let id = TextureId::new(read_u64(r)? as usize);
+        let width = read_u32(r)?;
+        let height = read_u32(r)?;
+        if width > MAX_TEXTURE_SIZE || height > MAX_TEXTURE_SIZE {
+            return Err(invalid_data("Texture is too large"));
+        }
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(4))
+            .ok_or_else(|| invalid_data("Texture is too large"))?;
+        // The size is not trusted for preallocation, because the data may be truncated
+        let mut pixels = Vec::new();
+        r.by_ref().take(len as u64).read_to_end(&mut pixels)?;
+        if pixels.len() != len {
+            return Err(invalid_data("Truncated texture"));
+        }
+        Ok(CapturedTexture {
+            id: id,
+            width: width,
+            height: height,
+            pixels: pixels,
+        })
+    }
+}
+
+impl OwnedDrawList {
+    /// Writes the draw list in the format used by `FrameCapture`.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write_u32(w, self.vtx_buffer.len() as u32)?;
+        for vtx in &self.vtx_buffer {
+            write_f32(w, vtx.pos.x)?;
+            write_f32(w, vtx.pos.y)?;
+            write_f32(w, vtx.uv.x)?;
+            write_f32(w, vtx.uv.y)?;
+            write_u32(w, vtx.col)?;
+        }
+        write_u32(w, self.idx_buffer.len() as u32)?;
+        for &idx in &self.idx_buffer {
+            write_u16(w, idx)?;
+        }
+        write_u32(w, self.cmd_buffer.len() as u32)?;
+        for cmd in &self.cmd_buffer {
+            if cmd.user_callback.is_some() {
+                // Owned draw lists contain no callbacks other than render state resets
+                write_u8(w, CMD_RESET_RENDER_STATE)?;
+            } else {
+                write_u8(w, CMD_ELEMENTS)?;
+                write_u32(w, cmd.elem_count)?;
+                write_f32(w, cmd.clip_rect.x)?;
+                write_f32(w, cmd.clip_rect.y)?;
+                write_f32(w, cmd.clip_rect.z)?;
+                write_f32(w, cmd.clip_rect.w)?;
+                write_u64(w, TextureId::from(cmd.texture_id).id() as u64)?;
+            }
+        }
+        Ok(())
+    }
+    /// Reads a draw list in the format used by `FrameCapture`.
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<OwnedDrawList> {
+        // Counts are not trusted for preallocation, because the data may be truncated
+        let mut vtx_buffer = Vec::new();
+        for _ in 0..read_u32(r)? {
+            vtx_buffer.push(ImDrawVert {
+                pos: ImVec2::new(read_f32(r)?, read_f32(r)?),
+                uv: ImVec2::new(read_f32(r)?, read_f32(r)?),
+                col: read_u32(r)?,
+            });
+        }
+        let mut idx_buffer = Vec::new();
+        for _ in 0..read_u32(r)? {
+            let idx = read_u16(r)?;
+            if idx as usize >= vtx_buffer.len() {
+                return Err(invalid_data("Vertex index out of range"));
+            }
+            idx_buffer.push(idx);
+        }
+        let mut cmd_buffer = Vec::new();
+        for _ in 0..read_u32(r)? {
+            let cmd = match read_u8(r)? {
+                CMD_ELEMENTS => {
+                    let elem_count = read_u32(r)?;
+                    let clip_rect = [read_f32(r)?, read_f32(r)?, read_f32(r)?, read_f32(r)?];
+                    let texture_id = TextureId::new(read_u64(r)? as usize);
+                    sys::ImDrawCmd {
+                        elem_count: elem_count,
+                        clip_rect: clip_rect.into(),
+                        texture_id: texture_id.as_raw(),
+                        user_callback: None,
+                        user_callback_data: ::std::ptr::null_mut(),
+                    }
+                }
+                CMD_RESET_RENDER_STATE => reset_render_state_cmd(),
+                _ => return Err(invalid_data("Invalid draw command")),
+            };
+            cmd_buffer.push(cmd);
+        }
+        let elem_count: u64 = cmd_buffer.iter().map(|cmd| cmd.elem_count as u64).sum();
+        if elem_count > idx_buffer.len() as u64 {
+            return Err(invalid_data("Draw commands use more indices than the draw list has"));
+        }
+        Ok(OwnedDrawList {
+            cmd_buffer: cmd_buffer,
+            idx_buffer: idx_buffer,
+            vtx_buffer: vtx_buffer,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor};
+
+    use render::{reset_render_state_cmd, OwnedDrawList};
+    use super::*;
+    use super::super::DrawCmd;
+
+    fn capture() -> FrameCapture {
+        let vertex = |x, y, col| ImDrawVert {
+            pos: ImVec2::new(x, y),
+            uv: ImVec2::new(x / 10.0, y / 10.0),
+            col: col,
+        };
+        let mut draw_data = OwnedDrawData::new((640.0, 480.0));
+        draw_data.draw_lists.push(OwnedDrawList {
+            cmd_buffer: vec![
+                sys::ImDrawCmd {
+                    elem_count: 3,
+                    clip_rect: [1.0, 2.0, 300.0, 400.0].into(),
+                    texture_id: TextureId::new(7).as_raw(),
+                    user_callback: None,
+                    user_callback_data: ::std::ptr::null_mut(),
+                },
+                reset_render_state_cmd(),
+            ],
+            idx_buffer: vec![0, 1, 2],
+            vtx_buffer: vec![
+                vertex(0.0, 0.0, 0xff00_00ff),
+                vertex(10.0, 0.0, 0xff00_ff00),
+                vertex(0.0, 10.0, 0xffff_0000),
+            ],
+        });
+        FrameCapture {
+            draw_data: draw_data,
+            font_texture: Some(CapturedTexture {
+                id: TextureId::new(1),
+                width: 2,
+                height: 1,
+                pixels: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            }),
+        }
+    }
+
+    fn encode(capture: &FrameCapture) -> Vec<u8> {
+        let mut data = Vec::new();
+        capture.write_to(&mut data).unwrap();
+        data
+    }
+
+    fn read_error(data: &[u8]) -> io::ErrorKind {
+        match FrameCapture::read_from(&mut Cursor::new(data)) {
+            Ok(_) => panic!("Invalid capture was read"),
+            Err(e) => e.kind(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let original = capture();
+        let mut loaded = FrameCapture::read_from(&mut Cursor::new(encode(&original))).unwrap();
+        assert_eq!(loaded.font_texture, original.font_texture);
+        assert_eq!(loaded.draw_data.display_size, (640.0, 480.0));
+
+        let draw_data = loaded.draw_data.draw_data();
+        let lists: Vec<_> = draw_data.draw_lists().collect();
+        assert_eq!(lists.len(), 1);
+        let expected = &original.draw_data.draw_lists[0];
+        assert_eq!(lists[0].idx_buffer, &expected.idx_buffer[..]);
+        assert_eq!(lists[0].vtx_buffer.len(), 3);
+        for (vtx, expected) in lists[0].vtx_buffer.iter().zip(&expected.vtx_buffer) {
+            assert_eq!((vtx.pos, vtx.uv, vtx.col), (expected.pos, expected.uv, expected.col));
+        }
+        let mut commands = lists[0].commands();
+        match commands.next() {
+            Some(DrawCmd::Elements {
+                count,
+                clip_rect,
+                texture_id,
+            }) => {
+                assert_eq!(count, 3);
+                assert_eq!(clip_rect, [1.0, 2.0, 300.0, 400.0]);
+                assert_eq!(texture_id, TextureId::new(7));
+            }
+            _ => panic!("Expected an elements command"),
+        }
+        match commands.next() {
+            Some(DrawCmd::ResetRenderState) => (),
+            _ => panic!("Expected a render state reset"),
+        }
+        assert!(commands.next().is_none());
+    }
+
+    #[test]
+    fn without_font_texture() {
+        let mut original = capture();
+        original.font_texture = None;
+        let loaded = FrameCapture::read_from(&mut Cursor::new(encode(&original))).unwrap();
+        assert_eq!(loaded.font_texture, None);
+        assert_eq!(loaded.draw_data.draw_lists.len(), 1);
+    }
+
+    #[test]
+    fn bad_magic() {
+        let mut data = encode(&capture());
+        data[0] = b'X';
+        assert_eq!(read_error(&data), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn bad_version() {
+        let mut data = encode(&capture());
+        data[MAGIC.len()] = VERSION as u8 + 1;
+        assert_eq!(read_error(&data), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn truncated_input() {
+        let data = encode(&capture());
+        for len in 0..data.len() {
+            assert_eq!(read_error(&data[..len]), io::ErrorKind::InvalidData, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn index_out_of_range() {
+        let mut original = capture();
+        original.draw_data.draw_lists[0].idx_buffer[2] = 3;
+        assert_eq!(read_error(&encode(&original)), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn too_many_elements() {
+        let mut original = capture();
+        original.draw_data.draw_lists[0].cmd_buffer[0].elem_count = 4;
+        assert_eq!(read_error(&encode(&original)), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn oversized_texture() {
+        let mut data = Vec::new();
+        write_u64(&mut data, 1).unwrap();
+        write_u32(&mut data, MAX_TEXTURE_SIZE + 1).unwrap();
+        write_u32(&mut data, 1).unwrap();
+        let result = CapturedTexture::read_from(&mut Cursor::new(data));
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -1,23 +1,22 @@
 use sys;
-use std::marker::PhantomData;
 
-use super::{ImVec2, ImGuiWindowFlags, Ui};
+use super::{ImGui, ImGuiWindowFlags, ImVec2, Ui};
 
 #[must_use]
 pub struct ChildFrame<'ui, 'p> {
     name: &'p str,
     size: ImVec2,
     flags: ImGuiWindowFlags,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> ChildFrame<'ui, 'p> {
-    pub fn new<S: Into<ImVec2>>(_: &Ui<'ui>, name: &'p str, size: S) -> ChildFrame<'ui, 'p> {
+    pub fn new<S: Into<ImVec2>>(ui: &Ui<'ui>, name: &'p str, size: S) -> ChildFrame<'ui, 'p> {
         ChildFrame {
             name: name,
             size: size.into(),
             flags: ImGuiWindowFlags::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -103,6 +102,7 @@ impl<'ui, 'p> ChildFrame<'ui, 'p> {
         self
     }
     pub fn build<F: FnOnce()>(self, f: F) {
+        self.imgui.make_current();
         // See issue for history.
         // https://github.com/Gekkio/imgui-rs/pull/58
         let show_border = false;
@@ -111,6 +111,7 @@ impl<'ui, 'p> ChildFrame<'ui, 'p> {
             unsafe { sys::igBeginChild(sys::ImStr::from(self.name), self.size, show_border, self.flags) };
         if render_child_frame {
             f();
+            self.imgui.make_current();
         }
         unsafe { sys::igEndChild() };
     }
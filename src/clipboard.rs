@@ -0,0 +1,75 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::ptr;
+
+/// A system clipboard, used by imgui for copying and pasting text.
+///
+/// Register one with `ImGui::set_clipboard_backend`. Without a backend, imgui keeps copied text
+/// in an internal buffer that is only visible to imgui itself.
+pub trait ClipboardBackend {
+    /// Returns the current clipboard text, or `None` if the clipboard is empty or doesn't
+    /// contain text.
+    fn get(&mut self) -> Option<String>;
+    /// Replaces the clipboard contents with the given text.
+    fn set(&mut self, value: &str);
+}
+
+/// The user data passed to the clipboard callbacks of `ImGuiIO`.
+pub(crate) struct ClipboardContext {
+    backend: Box<ClipboardBackend>,
+    // imgui expects the returned text to stay valid until the next call
+    last_value: CString,
+}
+
+impl ClipboardContext {
+    pub fn new(backend: Box<ClipboardBackend>) -> ClipboardContext {
+        ClipboardContext {
+            backend: backend,
+            last_value: CString::default(),
+        }
+    }
+}
+
+/// Converts a string to a C string, truncating it at the first nul byte.
+pub(crate) fn to_cstring(value: &str) -> CString {
+    let bytes = match value.find('\0') {
+        Some(end) => &value[..end],
+        None => value,
+    };
+    CString::new(bytes).unwrap()
+}
+
+/// Runs a callback called from C, and aborts the process if it panics, because panics must not
+/// unwind into imgui.
+fn abort_on_panic<F: FnOnce() -> R, R>(f: F) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(_) => {
+            eprintln!("Clipboard backend panicked");
+            process::abort();
+        }
+    }
+}
+
+pub(crate) extern "C" fn get_clipboard_text(user_data: *mut c_void) -> *const c_char {
+    abort_on_panic(|| {
+        let ctx = unsafe { &mut *(user_data as *mut ClipboardContext) };
+        match ctx.backend.get() {
+            Some(text) => {
+                ctx.last_value = to_cstring(&text);
+                ctx.last_value.as_ptr()
+            }
+            None => ptr::null(),
+        }
+    })
+}
+
+pub(crate) extern "C" fn set_clipboard_text(user_data: *mut c_void, text: *const c_char) {
+    abort_on_panic(|| {
+        let ctx = unsafe { &mut *(user_data as *mut ClipboardContext) };
+        let text = unsafe { CStr::from_ptr(text) };
+        ctx.backend.set(&text.to_string_lossy());
+    })
+}
@@ -1,9 +1,8 @@
 #![warn(missing_docs)]
 use sys;
-use std::marker::PhantomData;
 use std::ptr;
 
-use {ImGuiColorEditFlags, ImVec2, ImVec4, Ui};
+use {ImGui, ImGuiColorEditFlags, ImVec2, ImVec4, Ui};
 
 /// Mutable reference to an editable color value.
 #[derive(Debug)]
@@ -78,17 +77,17 @@ pub struct ColorEdit<'ui, 'p> {
     label: &'p str,
     value: EditableColor<'p>,
     flags: ImGuiColorEditFlags,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> ColorEdit<'ui, 'p> {
     /// Constructs a new color editor builder.
-    pub fn new(_: &Ui<'ui>, label: &'p str, value: EditableColor<'p>) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, value: EditableColor<'p>) -> Self {
         ColorEdit {
             label,
             value,
             flags: ImGuiColorEditFlags::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     /// Replaces all current settings with the given flags.
@@ -200,6 +199,7 @@ impl<'ui, 'p> ColorEdit<'ui, 'p> {
     }
     /// Builds the color editor.
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         match self.value {
             EditableColor::Float3(value) => unsafe {
                 sys::igColorEdit3(sys::ImStr::from(self.label), value.as_mut_ptr(), self.flags)
@@ -218,18 +218,18 @@ pub struct ColorPicker<'ui, 'p> {
     value: EditableColor<'p>,
     flags: ImGuiColorEditFlags,
     ref_color: Option<&'p [f32; 4]>,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> ColorPicker<'ui, 'p> {
     /// Constructs a new color picker builder.
-    pub fn new(_: &Ui<'ui>, label: &'p str, value: EditableColor<'p>) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, value: EditableColor<'p>) -> Self {
         ColorPicker {
             label,
             value,
             flags: ImGuiColorEditFlags::empty(),
             ref_color: None,
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     /// Replaces all current settings with the given flags.
@@ -346,6 +346,7 @@ impl<'ui, 'p> ColorPicker<'ui, 'p> {
     }
     /// Builds the color picker.
     pub fn build(mut self) -> bool {
+        self.imgui.make_current();
         if let EditableColor::Float3(_) = self.value {
             self.flags.insert(ImGuiColorEditFlags::NoAlpha);
         }
@@ -368,18 +369,18 @@ pub struct ColorButton<'ui, 'p> {
     color: ImVec4,
     flags: ImGuiColorEditFlags,
     size: ImVec2,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> ColorButton<'ui, 'p> {
     /// Constructs a new color button builder.
-    pub fn new(_: &Ui<'ui>, desc_id: &'p str, color: ImVec4) -> Self {
+    pub fn new(ui: &Ui<'ui>, desc_id: &'p str, color: ImVec4) -> Self {
         ColorButton {
             desc_id,
             color,
             flags: ImGuiColorEditFlags::empty(),
             size: ImVec2::zero(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     /// Replaces all current settings with the given flags.
@@ -423,6 +424,7 @@ impl<'ui, 'p> ColorButton<'ui, 'p> {
     }
     /// Builds the color button.
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igColorButton(sys::ImStr::from(self.desc_id), self.color, self.flags, self.size) }
     }
 }
@@ -0,0 +1,351 @@
+use sys;
+use allocator;
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::raw::c_int;
+use std::path::Path;
+use std::ptr;
+use std::rc::Rc;
+
+use super::{ImGui, ImVec2, Ui};
+
+/// Identifies a font in a font atlas.
+///
+/// Font ids are returned when fonts are added to a `FontAtlas`, and stay valid until the atlas
+/// is cleared.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FontId(usize);
+
+/// Set of glyphs that are rasterized when a font is added to an atlas.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FontGlyphRange {
+    /// Basic Latin + Latin Supplement.
+    Default,
+    /// Default + Korean characters.
+    Korean,
+    /// Default + Hiragana, Katakana, Half-Width, Selection of 1946 Ideographs.
+    Japanese,
+    /// Default + Japanese + full set of about 21000 CJK Unified Ideographs.
+    Chinese,
+    /// Default + about 400 Cyrillic characters.
+    Cyrillic,
+    /// Default + Thai characters.
+    Thai,
+    /// Custom list of inclusive (first, last) codepoint pairs, terminated by a single zero.
+    ///
+    /// For example, `&[0xf000, 0xf3ff, 0]` covers the private use area used by many icon
+    /// fonts.
+    Custom(&'static [u16]),
+}
+
+impl FontGlyphRange {
+    fn as_ptr(self, atlas: *mut sys::ImFontAtlas) -> *const sys::ImWchar {
+        use self::FontGlyphRange::*;
+        unsafe {
+            match self {
+                Default => sys::ImFontAtlas_GetGlyphRangesDefault(atlas),
+                Korean => sys::ImFontAtlas_GetGlyphRangesKorean(atlas),
+                Japanese => sys::ImFontAtlas_GetGlyphRangesJapanese(atlas),
+                Chinese => sys::ImFontAtlas_GetGlyphRangesChinese(atlas),
+                Cyrillic => sys::ImFontAtlas_GetGlyphRangesCyrillic(atlas),
+                Thai => sys::ImFontAtlas_GetGlyphRangesThai(atlas),
+                Custom(ranges) => {
+                    assert!(
+                        ranges.len() % 2 == 1 && ranges[ranges.len() - 1] == 0,
+                        "Custom glyph ranges must be (first, last) pairs followed by a zero"
+                    );
+                    ranges.as_ptr()
+                }
+            }
+        }
+    }
+}
+
+/// Configuration used when adding a font to a `FontAtlas`.
+#[derive(Copy, Clone, Debug)]
+pub struct FontConfig {
+    size_pixels: f32,
+    oversample_h: i32,
+    oversample_v: i32,
+    pixel_snap_h: bool,
+    glyph_extra_spacing: ImVec2,
+    glyph_offset: ImVec2,
+    glyph_ranges: FontGlyphRange,
+    merge_mode: bool,
+    rasterizer_multiply: f32,
+}
+
+impl Default for FontConfig {
+    fn default() -> FontConfig {
+        FontConfig {
+            size_pixels: 13.0,
+            oversample_h: 3,
+            oversample_v: 1,
+            pixel_snap_h: false,
+            glyph_extra_spacing: ImVec2::zero(),
+            glyph_offset: ImVec2::zero(),
+            glyph_ranges: FontGlyphRange::Default,
+            merge_mode: false,
+            rasterizer_multiply: 1.0,
+        }
+    }
+}
+
+impl FontConfig {
+    /// Creates a font configuration with the default values.
+    pub fn new() -> FontConfig { FontConfig::default() }
+    /// Sets the font size in pixels.
+    #[inline]
+    pub fn size_pixels(mut self, size_pixels: f32) -> Self {
+        self.size_pixels = size_pixels;
+        self
+    }
+    /// Sets how many times glyphs are rasterized at a higher horizontal resolution for
+    /// sub-pixel positioning.
+    #[inline]
+    pub fn oversample_h(mut self, oversample_h: i32) -> Self {
+        self.oversample_h = oversample_h;
+        self
+    }
+    /// Sets how many times glyphs are rasterized at a higher vertical resolution.
+    #[inline]
+    pub fn oversample_v(mut self, oversample_v: i32) -> Self {
+        self.oversample_v = oversample_v;
+        self
+    }
+    /// Aligns every glyph to the pixel boundary horizontally. Useful when merging a bitmap
+    /// font with a vector font. Overrides horizontal oversampling.
+    #[inline]
+    pub fn pixel_snap_h(mut self, pixel_snap_h: bool) -> Self {
+        self.pixel_snap_h = pixel_snap_h;
+        self
+    }
+    /// Sets the extra spacing between glyphs (in pixels).
+    #[inline]
+    pub fn glyph_extra_spacing<S: Into<ImVec2>>(mut self, spacing: S) -> Self {
+        self.glyph_extra_spacing = spacing.into();
+        self
+    }
+    /// Sets the offset of all glyphs of this font (in pixels).
+    #[inline]
+    pub fn glyph_offset<O: Into<ImVec2>>(mut self, offset: O) -> Self {
+        self.glyph_offset = offset.into();
+        self
+    }
+    /// Sets the glyphs that will be rasterized.
+    #[inline]
+    pub fn glyph_ranges(mut self, glyph_ranges: FontGlyphRange) -> Self {
+        self.glyph_ranges = glyph_ranges;
+        self
+    }
+    /// Merges the glyphs into the previously added font instead of creating a new font.
+    ///
+    /// This is typically used to add icon glyphs to a text font.
+    #[inline]
+    pub fn merge_mode(mut self, merge_mode: bool) -> Self {
+        self.merge_mode = merge_mode;
+        self
+    }
+    /// Brightens (> 1.0) or darkens (< 1.0) the rasterized glyphs.
+    #[inline]
+    pub fn rasterizer_multiply(mut self, rasterizer_multiply: f32) -> Self {
+        self.rasterizer_multiply = rasterizer_multiply;
+        self
+    }
+    fn to_raw(&self, atlas: *mut sys::ImFontAtlas) -> sys::ImFontConfig {
+        let mut raw = sys::ImFontConfig::default();
+        raw.size_pixels = self.size_pixels;
+        raw.oversample_h = self.oversample_h;
+        raw.oversample_v = self.oversample_v;
+        raw.pixel_snap_h = self.pixel_snap_h;
+        raw.glyph_extra_spacing = self.glyph_extra_spacing;
+        raw.glyph_offset = self.glyph_offset;
+        raw.glyph_ranges = self.glyph_ranges.as_ptr(atlas);
+        raw.merge_mode = self.merge_mode;
+        raw.rasterizer_multiply = self.rasterizer_multiply;
+        raw
+    }
+}
+
+/// Builder for the fonts of a font atlas.
+///
+/// Fonts must be added before the font texture is built with `ImGui::prepare_texture`.
+pub struct FontAtlas<'a> {
+    imgui: &'a mut ImGui,
+}
+
+impl<'a> FontAtlas<'a> {
+    pub(crate) fn new(imgui: &'a mut ImGui) -> FontAtlas<'a> { FontAtlas { imgui } }
+    fn raw(&self) -> *mut sys::ImFontAtlas { self.imgui.font_atlas.raw() }
+    /// Adds the embedded default font (ProggyClean) to the atlas.
+    pub fn add_default_font(&mut self) -> FontId {
+        self.add_default_font_with_config(&FontConfig::default())
+    }
+    /// Adds the embedded default font (ProggyClean) to the atlas using the given configuration.
+    ///
+    /// # Panics
+    /// Panics if merge mode is enabled but the atlas has no font to merge into.
+    pub fn add_default_font_with_config(&mut self, config: &FontConfig) -> FontId {
+        self.check_merge_mode(config);
+        let atlas = self.raw();
+        let raw_config = config.to_raw(atlas);
+        let font = self.imgui.with_context(|| unsafe {
+            sys::ImFontAtlas_AddFontDefault(atlas, &raw_config)
+        });
+        self.font_id(font)
+    }
+    /// Adds a TrueType font from a byte slice.
+    ///
+    /// The data is copied, so the slice doesn't need to outlive the atlas.
+    ///
+    /// # Panics
+    /// Panics if merge mode is enabled but the atlas has no font to merge into.
+    pub fn add_font_from_memory(&mut self, data: &[u8], config: &FontConfig) -> FontId {
+        self.check_merge_mode(config);
+        let atlas = self.raw();
+        let raw_config = config.to_raw(atlas);
+        let font = self.imgui.with_context(|| unsafe {
+            // The atlas takes ownership of the copy and frees it when it's cleared
+            let font_data = sys::igMemAlloc(data.len());
+            ptr::copy_nonoverlapping(data.as_ptr(), font_data as *mut u8, data.len());
+            sys::ImFontAtlas_AddFontFromMemoryTTF(
+                atlas,
+                font_data,
+                data.len() as c_int,
+                raw_config.size_pixels,
+                &raw_config,
+                ptr::null(),
+            )
+        });
+        self.font_id(font)
+    }
+    /// Adds a TrueType font from a file.
+    ///
+    /// # Panics
+    /// Panics if merge mode is enabled but the atlas has no font to merge into.
+    pub fn add_font_from_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: &FontConfig,
+    ) -> io::Result<FontId> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        Ok(self.add_font_from_memory(&data, config))
+    }
+    /// Removes all fonts and frees all font data and texture data.
+    ///
+    /// This invalidates all existing font ids. Fonts can be added again afterwards (e.g. at a
+    /// different size after a DPI change), after which the font texture must be rebuilt and
+    /// uploaded again by the renderer.
+    pub fn clear(&mut self) {
+        let atlas = self.raw();
+        self.imgui.with_context(|| unsafe { sys::ImFontAtlas_Clear(atlas) });
+    }
+    /// Frees the CPU copy of the font texture, but keeps the fonts.
+    ///
+    /// This can be used to save memory after the texture has been uploaded to the GPU. The
+    /// texture data is rebuilt by `ImGui::prepare_texture` if it's needed again.
+    pub fn clear_tex_data(&mut self) {
+        let atlas = self.raw();
+        self.imgui.with_context(|| unsafe { sys::ImFontAtlas_ClearTexData(atlas) });
+    }
+    /// Returns the number of fonts in the atlas.
+    pub fn font_count(&self) -> usize {
+        unsafe { sys::ImFontAtlas_Fonts_size(self.raw()) as usize }
+    }
+    /// Returns the ids of all fonts in the atlas.
+    pub fn fonts(&self) -> Vec<FontId> { (0..self.font_count()).map(FontId).collect() }
+    fn check_merge_mode(&self, config: &FontConfig) {
+        assert!(
+            !config.merge_mode || self.font_count() > 0,
+            "Merge mode requires a previously added font to merge into"
+        );
+    }
+    fn font_id(&self, font: *mut sys::ImFont) -> FontId {
+        let atlas = self.raw();
+        (0..self.font_count())
+            .find(|&index| unsafe { sys::ImFontAtlas_Fonts_index(atlas, index as c_int) == font })
+            .map(FontId)
+            .expect("Font was not added to the atlas")
+    }
+}
+
+/// A font atlas that can be shared between several `ImGui` contexts.
+///
+/// Every `ImGui` instance owns a font atlas. By default it is private to the instance, but an
+/// atlas can be created up front and passed to `ImGui::init_with_shared_font_atlas` so that
+/// the same fonts (and the same font texture) are used by several contexts. The atlas is
+/// released when the last context or handle using it is dropped.
+///
+/// Since the font texture id is stored in the atlas, all contexts sharing an atlas must also be
+/// able to use the same uploaded texture.
+#[derive(Clone)]
+pub struct SharedFontAtlas(Rc<FontAtlasStorage>);
+
+struct FontAtlasStorage {
+    raw: *mut sys::ImFontAtlas,
+}
+
+impl SharedFontAtlas {
+    /// Creates a new empty font atlas.
+    pub fn create() -> SharedFontAtlas {
+        let raw = Box::into_raw(Box::new(sys::ImFontAtlas::default()));
+        SharedFontAtlas(Rc::new(FontAtlasStorage { raw }))
+    }
+    /// Returns the number of contexts and handles currently using this atlas.
+    pub fn use_count(&self) -> usize { Rc::strong_count(&self.0) }
+    pub(crate) fn raw(&self) -> *mut sys::ImFontAtlas { self.0.raw }
+    /// Returns the font identified by the given id.
+    ///
+    /// # Panics
+    /// Panics if the atlas doesn't contain the font (e.g. because the atlas has been cleared).
+    pub(crate) fn font(&self, id: FontId) -> *mut sys::ImFont {
+        unsafe {
+            let count = sys::ImFontAtlas_Fonts_size(self.raw()) as usize;
+            assert!(id.0 < count, "Font {:?} is not in the font atlas", id);
+            sys::ImFontAtlas_Fonts_index(self.raw(), id.0 as c_int)
+        }
+    }
+}
+
+impl Drop for FontAtlasStorage {
+    fn drop(&mut self) {
+        unsafe {
+            // The atlas frees its memory through the current context, so we need a temporary
+            // one if every context has already been destroyed
+            let temp_ctx = if sys::igGetCurrentContext().is_null() {
+                let ctx = allocator::create_context();
+                sys::igSetCurrentContext(ctx);
+                ctx
+            } else {
+                ptr::null_mut()
+            };
+            sys::ImFontAtlas_Clear(self.raw);
+            if !temp_ctx.is_null() {
+                sys::igDestroyContext(temp_ctx);
+            }
+            drop(Box::from_raw(self.raw));
+        }
+    }
+}
+
+/// Tracks a font pushed to the font stack.
+///
+/// The font is popped from the stack when the token is dropped or `pop` is called.
+#[must_use]
+pub struct FontToken<'ui> {
+    imgui: &'ui ImGui,
+}
+
+impl<'ui> FontToken<'ui> {
+    pub(crate) fn new(ui: &Ui<'ui>) -> FontToken<'ui> { FontToken { imgui: ui.imgui } }
+    /// Pops the font from the font stack.
+    pub fn pop(self) {}
+}
+
+impl<'ui> Drop for FontToken<'ui> {
+    fn drop(&mut self) {
+        self.imgui.make_current();
+        unsafe { sys::igPopFont() }
+    }
+}
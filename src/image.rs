@@ -0,0 +1,160 @@
+#![warn(missing_docs)]
+use sys;
+use std::os::raw::c_int;
+
+use super::{ImGui, ImVec2, ImVec4, TextureId, Ui};
+
+/// Image widget.
+#[must_use]
+pub struct Image<'ui> {
+    texture_id: TextureId,
+    size: ImVec2,
+    uv0: ImVec2,
+    uv1: ImVec2,
+    tint_col: ImVec4,
+    border_col: ImVec4,
+    imgui: &'ui ImGui,
+}
+
+impl<'ui> Image<'ui> {
+    /// Creates an image widget that draws the given texture with the given size.
+    pub fn new<S: Into<ImVec2>>(ui: &Ui<'ui>, texture_id: TextureId, size: S) -> Self {
+        Image {
+            texture_id: texture_id,
+            size: size.into(),
+            uv0: ImVec2::new(0.0, 0.0),
+            uv1: ImVec2::new(1.0, 1.0),
+            tint_col: ImVec4::new(1.0, 1.0, 1.0, 1.0),
+            border_col: ImVec4::new(0.0, 0.0, 0.0, 0.0),
+            imgui: ui.imgui,
+        }
+    }
+
+    /// Sets the texture coordinates of the top left corner (default: `[0.0, 0.0]`).
+    #[inline]
+    pub fn uv0<T: Into<ImVec2>>(mut self, uv0: T) -> Self {
+        self.uv0 = uv0.into();
+        self
+    }
+
+    /// Sets the texture coordinates of the bottom right corner (default: `[1.0, 1.0]`).
+    #[inline]
+    pub fn uv1<T: Into<ImVec2>>(mut self, uv1: T) -> Self {
+        self.uv1 = uv1.into();
+        self
+    }
+
+    /// Sets the color the texture is multiplied with (default: opaque white).
+    #[inline]
+    pub fn tint_col<C: Into<ImVec4>>(mut self, tint_col: C) -> Self {
+        self.tint_col = tint_col.into();
+        self
+    }
+
+    /// Sets the border color. The border is not drawn if the color is fully transparent
+    /// (default).
+    #[inline]
+    pub fn border_col<C: Into<ImVec4>>(mut self, border_col: C) -> Self {
+        self.border_col = border_col.into();
+        self
+    }
+
+    /// Draws the image.
+    pub fn build(self) {
+        self.imgui.make_current();
+        unsafe {
+            sys::igImage(
+                self.texture_id.as_raw(),
+                self.size,
+                self.uv0,
+                self.uv1,
+                self.tint_col,
+                self.border_col,
+            );
+        }
+    }
+}
+
+/// Image button widget.
+#[must_use]
+pub struct ImageButton<'ui> {
+    texture_id: TextureId,
+    size: ImVec2,
+    uv0: ImVec2,
+    uv1: ImVec2,
+    frame_padding: i32,
+    bg_col: ImVec4,
+    tint_col: ImVec4,
+    imgui: &'ui ImGui,
+}
+
+impl<'ui> ImageButton<'ui> {
+    /// Creates an image button that draws the given texture with the given size.
+    ///
+    /// The texture id is also used as the widget id, so use `Ui::with_id` if the same texture
+    /// is used for several buttons in the same window.
+    pub fn new<S: Into<ImVec2>>(ui: &Ui<'ui>, texture_id: TextureId, size: S) -> Self {
+        ImageButton {
+            texture_id: texture_id,
+            size: size.into(),
+            uv0: ImVec2::new(0.0, 0.0),
+            uv1: ImVec2::new(1.0, 1.0),
+            frame_padding: -1,
+            bg_col: ImVec4::new(0.0, 0.0, 0.0, 0.0),
+            tint_col: ImVec4::new(1.0, 1.0, 1.0, 1.0),
+            imgui: ui.imgui,
+        }
+    }
+
+    /// Sets the texture coordinates of the top left corner (default: `[0.0, 0.0]`).
+    #[inline]
+    pub fn uv0<T: Into<ImVec2>>(mut self, uv0: T) -> Self {
+        self.uv0 = uv0.into();
+        self
+    }
+
+    /// Sets the texture coordinates of the bottom right corner (default: `[1.0, 1.0]`).
+    #[inline]
+    pub fn uv1<T: Into<ImVec2>>(mut self, uv1: T) -> Self {
+        self.uv1 = uv1.into();
+        self
+    }
+
+    /// Sets the frame padding in pixels. Negative values use the frame padding of the current
+    /// style (default).
+    #[inline]
+    pub fn frame_padding(mut self, frame_padding: i32) -> Self {
+        self.frame_padding = frame_padding;
+        self
+    }
+
+    /// Sets the background color drawn behind the image (default: fully transparent).
+    #[inline]
+    pub fn bg_col<C: Into<ImVec4>>(mut self, bg_col: C) -> Self {
+        self.bg_col = bg_col.into();
+        self
+    }
+
+    /// Sets the color the texture is multiplied with (default: opaque white).
+    #[inline]
+    pub fn tint_col<C: Into<ImVec4>>(mut self, tint_col: C) -> Self {
+        self.tint_col = tint_col.into();
+        self
+    }
+
+    /// Draws the image button. Returns true if the button was clicked.
+    pub fn build(self) -> bool {
+        self.imgui.make_current();
+        unsafe {
+            sys::igImageButton(
+                self.texture_id.as_raw(),
+                self.size,
+                self.uv0,
+                self.uv1,
+                self.frame_padding as c_int,
+                self.bg_col,
+                self.tint_col,
+            )
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::cell::RefCell;
+use std::os::raw::c_int;
+
+use sys;
+use super::{ImGuiCol, ImVec2};
+
+/// IME state of a context. `ImGuiIO::ime_window_handle` points to it, because imgui doesn't
+/// pass any user data to `ime_set_input_screen_pos_fn`.
+#[derive(Default)]
+pub(crate) struct ImeState {
+    handler: Option<Box<FnMut(f32, f32)>>,
+    pos: Option<(f32, f32)>,
+    preedit: String,
+}
+
+pub(crate) type ImeCell = RefCell<ImeState>;
+
+impl ImeState {
+    pub fn set_handler(&mut self, handler: Box<FnMut(f32, f32)>) {
+        self.handler = Some(handler);
+    }
+    pub fn set_preedit(&mut self, text: &str) {
+        self.preedit.clear();
+        self.preedit.push_str(text);
+    }
+    pub fn preedit(&self) -> &str { &self.preedit }
+    pub fn pos(&self) -> Option<(f32, f32)> { self.pos }
+}
+
+/// Returns the IME state of the current context.
+unsafe fn current() -> Option<&'static ImeCell> {
+    let io = sys::igGetIO();
+    if io.is_null() || (*io).ime_window_handle.is_null() {
+        None
+    } else {
+        Some(&*((*io).ime_window_handle as *const ImeCell))
+    }
+}
+
+pub(crate) extern "C" fn set_input_screen_pos(x: c_int, y: c_int) {
+    if let Some(cell) = unsafe { current() } {
+        let mut state = cell.borrow_mut();
+        let pos = (x as f32, y as f32);
+        state.pos = Some(pos);
+        if let Some(ref mut handler) = state.handler {
+            handler(pos.0, pos.1);
+        }
+    }
+}
+
+/// Draws the pre-edit text over the last item if it is an active text input.
+///
+/// imgui 1.52 has no support for showing text that is still being composed, so the text is
+/// drawn over the input at the position last reported to the IME handler.
+pub(crate) fn draw_preedit_over_active_item() {
+    unsafe {
+        if !sys::igIsItemActive() {
+            return;
+        }
+        let cell = match current() {
+            Some(cell) => cell,
+            None => return,
+        };
+        let state = cell.borrow();
+        if state.preedit.is_empty() {
+            return;
+        }
+        let pos = match state.pos {
+            Some((x, y)) => ImVec2::new(x + 1.0, y),
+            None => {
+                let mut min = ImVec2::new(0.0, 0.0);
+                sys::igGetItemRectMin(&mut min);
+                min
+            }
+        };
+        let mut size = ImVec2::new(0.0, 0.0);
+        sys::igCalcTextSize(&mut size, sys::ImStr::from(state.preedit.as_str()), false, -1.0);
+        let max = ImVec2::new(pos.x + size.x, pos.y + size.y);
+
+        let draw_list = sys::igGetWindowDrawList();
+        sys::ImDrawList_PushClipRectFullScreen(draw_list);
+        sys::ImDrawList_AddRectFilled(
+            draw_list,
+            pos,
+            max,
+            sys::igGetColorU32(ImGuiCol::PopupBg, 1.0),
+            0.0,
+            0,
+        );
+        sys::ImDrawList_AddText(
+            draw_list,
+            pos,
+            sys::igGetColorU32(ImGuiCol::Text, 1.0),
+            sys::ImStr::from(state.preedit.as_str()),
+        );
+        sys::ImDrawList_AddLine(
+            draw_list,
+            ImVec2::new(pos.x, max.y),
+            max,
+            sys::igGetColorU32(ImGuiCol::Text, 1.0),
+            1.0,
+        );
+        sys::ImDrawList_PopClipRect(draw_list);
+    }
+}
@@ -1,8 +1,8 @@
 use sys;
-use std::marker::PhantomData;
 use std::ptr;
 
-use super::{ImGuiInputTextFlags, ImString, Ui};
+use ime;
+use super::{ImGui, ImGuiInputTextFlags, ImString, Ui};
 
 macro_rules! impl_text_flags {
     ($InputType:ident) => {
@@ -123,16 +123,16 @@ pub struct InputText<'ui, 'p> {
     label: &'p str,
     buf: &'p mut ImString,
     flags: ImGuiInputTextFlags,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> InputText<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str, buf: &'p mut ImString) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, buf: &'p mut ImString) -> Self {
         InputText {
             label: label,
             buf: buf,
             flags: ImGuiInputTextFlags::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
 
@@ -142,7 +142,8 @@ impl<'ui, 'p> InputText<'ui, 'p> {
     // pub fn callback(self) -> Self { }
 
     pub fn build(self) -> bool {
-        unsafe {
+        self.imgui.make_current();
+        let changed = unsafe {
             sys::igInputText(
                 sys::ImStr::from(self.label),
                 self.buf.as_mut_ptr(),
@@ -151,7 +152,9 @@ impl<'ui, 'p> InputText<'ui, 'p> {
                 None,
                 ptr::null_mut(),
             )
-        }
+        };
+        ime::draw_preedit_over_active_item();
+        changed
     }
 }
 
@@ -162,22 +165,23 @@ pub struct InputInt<'ui, 'p> {
     step: i32,
     step_fast: i32,
     flags: ImGuiInputTextFlags,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> InputInt<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut i32) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut i32) -> Self {
         InputInt {
             label: label,
             value: value,
             step: 1,
             step_fast: 100,
             flags: ImGuiInputTextFlags::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
 
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         unsafe {
             sys::igInputInt(
                 sys::ImStr::from(self.label),
@@ -201,11 +205,11 @@ pub struct InputFloat<'ui, 'p> {
     step_fast: f32,
     decimal_precision: i32,
     flags: ImGuiInputTextFlags,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> InputFloat<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut f32) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut f32) -> Self {
         InputFloat {
             label: label,
             value: value,
@@ -213,11 +217,12 @@ impl<'ui, 'p> InputFloat<'ui, 'p> {
             step_fast: 0.0,
             decimal_precision: -1,
             flags: ImGuiInputTextFlags::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
 
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         unsafe {
             sys::igInputFloat(
                 sys::ImStr::from(self.label),
@@ -243,21 +248,22 @@ macro_rules! impl_input_floatn {
             value: &'p mut [f32;$N],
             decimal_precision: i32,
             flags: ImGuiInputTextFlags,
-            _phantom: PhantomData<&'ui Ui<'ui>>
+            imgui: &'ui ImGui
         }
 
         impl<'ui, 'p> $InputFloatN<'ui, 'p> {
-            pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut [f32;$N]) -> Self {
+            pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut [f32;$N]) -> Self {
                 $InputFloatN {
                     label: label,
                     value: value,
                     decimal_precision: -1,
                     flags: ImGuiInputTextFlags::empty(),
-                    _phantom: PhantomData
+                    imgui: ui.imgui
                 }
             }
 
             pub fn build(self) -> bool {
+                self.imgui.make_current();
                 unsafe {
                     sys::$igInputFloatN(
                         sys::ImStr::from(self.label),
@@ -284,20 +290,21 @@ macro_rules! impl_input_intn {
             label: &'p str,
             value: &'p mut [i32;$N],
             flags: ImGuiInputTextFlags,
-            _phantom: PhantomData<&'ui Ui<'ui>>
+            imgui: &'ui ImGui
         }
 
         impl<'ui, 'p> $InputIntN<'ui, 'p> {
-            pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut [i32;$N]) -> Self {
+            pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut [i32;$N]) -> Self {
                 $InputIntN {
                     label: label,
                     value: value,
                     flags: ImGuiInputTextFlags::empty(),
-                    _phantom: PhantomData
+                    imgui: ui.imgui
                 }
             }
 
             pub fn build(self) -> bool {
+                self.imgui.make_current();
                 unsafe {
                     sys::$igInputIntN(
                         sys::ImStr::from(self.label),
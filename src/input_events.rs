@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use super::{ImGui, Key, MouseButton};
+
+/// An input event queued with `ImGui::push_event`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    /// The mouse moved to a position in display coordinates
+    MousePos(f32, f32),
+    /// A mouse button was pressed (`true`) or released (`false`)
+    MouseButton(MouseButton, bool),
+    /// The mouse wheel was scrolled by the given number of lines
+    MouseWheel(f32),
+    /// A key was pressed (`true`) or released (`false`)
+    Key(Key, bool),
+    /// A character was typed
+    Char(char),
+    /// The window gained (`true`) or lost (`false`) focus. Losing focus releases all keys and
+    /// mouse buttons
+    Focus(bool),
+}
+
+/// Queued input events that haven't been applied to imgui yet.
+#[derive(Debug, Default)]
+pub(crate) struct InputQueue {
+    events: VecDeque<InputEvent>,
+}
+
+/// What has been applied to imgui so far during one frame.
+#[derive(Default)]
+struct Applied {
+    mouse_moved: bool,
+    mouse_buttons: [bool; 5],
+    mouse_wheeled: bool,
+    keys: Vec<Key>,
+    text: bool,
+}
+
+impl Applied {
+    fn any_mouse_button(&self) -> bool { self.mouse_buttons.iter().any(|&b| b) }
+    fn can_apply(&self, event: &InputEvent) -> bool {
+        match *event {
+            InputEvent::MousePos(..) => {
+                !(self.any_mouse_button() || self.mouse_wheeled || !self.keys.is_empty() ||
+                      self.text)
+            }
+            InputEvent::MouseButton(button, _) => {
+                !(self.mouse_buttons[button.index()] || self.mouse_wheeled)
+            }
+            InputEvent::MouseWheel(_) => !(self.mouse_moved || self.any_mouse_button()),
+            InputEvent::Key(key, _) => {
+                !(self.keys.contains(&key) || self.text || self.any_mouse_button())
+            }
+            InputEvent::Char(_) => {
+                !(!self.keys.is_empty() || self.any_mouse_button() || self.mouse_moved ||
+                      self.mouse_wheeled)
+            }
+            InputEvent::Focus(_) => true,
+        }
+    }
+}
+
+impl InputQueue {
+    pub fn push(&mut self, event: InputEvent) { self.events.push_back(event); }
+    pub fn len(&self) -> usize { self.events.len() }
+    pub fn clear(&mut self) { self.events.clear(); }
+    /// Applies queued events to imgui until an event would hide the effect of an earlier one
+    /// from this frame, e.g. a release of a button that was pressed this frame. The remaining
+    /// events stay queued for the next frames.
+    pub fn apply(&mut self, imgui: &mut ImGui) {
+        let mut applied = Applied::default();
+        while let Some(&event) = self.events.front() {
+            if !applied.can_apply(&event) {
+                break;
+            }
+            self.events.pop_front();
+            match event {
+                InputEvent::MousePos(x, y) => {
+                    imgui.set_mouse_pos(x, y);
+                    applied.mouse_moved = true;
+                }
+                InputEvent::MouseButton(button, pressed) => {
+                    let mut states = imgui.io().mouse_down;
+                    states[button.index()] = pressed;
+                    imgui.set_mouse_down(&states);
+                    applied.mouse_buttons[button.index()] = true;
+                }
+                InputEvent::MouseWheel(delta) => {
+                    let wheel = imgui.io().mouse_wheel + delta;
+                    imgui.set_mouse_wheel(wheel);
+                    applied.mouse_wheeled = true;
+                }
+                InputEvent::Key(key, pressed) => {
+                    imgui.set_key(key, pressed);
+                    applied.keys.push(key);
+                }
+                InputEvent::Char(c) => {
+                    imgui.add_input_character(c);
+                    applied.text = true;
+                }
+                InputEvent::Focus(true) => (),
+                InputEvent::Focus(false) => {
+                    // Released through the setters so that input recordings see the releases
+                    let keys_down = imgui.io().keys_down;
+                    for (index, &down) in keys_down.iter().enumerate() {
+                        if let (true, Some(key)) = (down, Key::from_index(index)) {
+                            imgui.set_key(key, false);
+                        }
+                    }
+                    imgui.set_key_ctrl(false);
+                    imgui.set_key_shift(false);
+                    imgui.set_key_alt(false);
+                    imgui.set_key_super(false);
+                    imgui.set_mouse_down(&[false; 5]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::mem;
+
+use sys::ImGuiKey;
+
+/// A key on the keyboard.
+///
+/// Keys are stored in `ImGuiIO::keys_down` at the index of their discriminant, and the imgui
+/// key map is set up to match when an `ImGui` instance is created.
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    Tab,
+    LeftArrow,
+    RightArrow,
+    UpArrow,
+    DownArrow,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Insert,
+    Delete,
+    Backspace,
+    Space,
+    Enter,
+    Escape,
+    LeftCtrl,
+    LeftShift,
+    LeftAlt,
+    LeftSuper,
+    RightCtrl,
+    RightShift,
+    RightAlt,
+    RightSuper,
+    Menu,
+    Alpha0,
+    Alpha1,
+    Alpha2,
+    Alpha3,
+    Alpha4,
+    Alpha5,
+    Alpha6,
+    Alpha7,
+    Alpha8,
+    Alpha9,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Apostrophe,
+    Comma,
+    Minus,
+    Period,
+    Slash,
+    Semicolon,
+    Equal,
+    LeftBracket,
+    Backslash,
+    RightBracket,
+    GraveAccent,
+    CapsLock,
+    ScrollLock,
+    NumLock,
+    PrintScreen,
+    Pause,
+    Keypad0,
+    Keypad1,
+    Keypad2,
+    Keypad3,
+    Keypad4,
+    Keypad5,
+    Keypad6,
+    Keypad7,
+    Keypad8,
+    Keypad9,
+    KeypadDecimal,
+    KeypadDivide,
+    KeypadMultiply,
+    KeypadSubtract,
+    KeypadAdd,
+    KeypadEnter,
+    KeypadEqual,
+}
+
+impl Key {
+    /// Returns the index of the key in `ImGuiIO::keys_down`.
+    pub fn index(self) -> usize { self as usize }
+    /// Returns the key at an index of `ImGuiIO::keys_down`, if there is one.
+    pub fn from_index(index: usize) -> Option<Key> {
+        if index <= Key::KeypadEqual as usize {
+            Some(unsafe { mem::transmute::<u32, Key>(index as u32) })
+        } else {
+            None
+        }
+    }
+
+    /// The keys imgui needs to know about, and the keys they are mapped to.
+    pub(crate) const IMGUI_KEY_MAP: [(ImGuiKey, Key); 19] = [
+        (ImGuiKey::Tab, Key::Tab),
+        (ImGuiKey::LeftArrow, Key::LeftArrow),
+        (ImGuiKey::RightArrow, Key::RightArrow),
+        (ImGuiKey::UpArrow, Key::UpArrow),
+        (ImGuiKey::DownArrow, Key::DownArrow),
+        (ImGuiKey::PageUp, Key::PageUp),
+        (ImGuiKey::PageDown, Key::PageDown),
+        (ImGuiKey::Home, Key::Home),
+        (ImGuiKey::End, Key::End),
+        (ImGuiKey::Delete, Key::Delete),
+        (ImGuiKey::Backspace, Key::Backspace),
+        (ImGuiKey::Enter, Key::Enter),
+        (ImGuiKey::Escape, Key::Escape),
+        (ImGuiKey::A, Key::A),
+        (ImGuiKey::C, Key::C),
+        (ImGuiKey::V, Key::V),
+        (ImGuiKey::X, Key::X),
+        (ImGuiKey::Y, Key::Y),
+        (ImGuiKey::Z, Key::Z),
+    ];
+}
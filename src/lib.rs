@@ -1,5 +1,6 @@
 pub extern crate imgui_sys as sys;
 
+use std::cell::{RefCell, UnsafeCell};
 use std::ffi::CStr;
 use std::mem;
 use std::os::raw::{c_float, c_int, c_uchar, c_void};
@@ -7,40 +8,87 @@ use std::ptr;
 use std::slice;
 use std::str;
 use sys::ImGuiStyleVar;
+use clipboard::ClipboardContext;
+use ime::{ImeCell, ImeState};
+use input_events::InputQueue;
+use render::DrawCallbackFn;
+use settings::SettingsStore;
 
-pub use sys::{ImDrawIdx, ImDrawVert, ImGuiColorEditFlags, ImGuiHoveredFlags, ImGuiInputTextFlags,
-              ImGuiKey, ImGuiSelectableFlags, ImGuiCond, ImGuiCol, ImGuiStyle, ImGuiTreeNodeFlags,
-              ImGuiWindowFlags, ImVec2, ImVec4};
+pub use sys::{ImColor, ImDrawCornerFlags, ImDrawIdx, ImDrawVert, ImGuiColorEditFlags,
+              ImGuiHoveredFlags, ImGuiInputTextFlags, ImGuiKey, ImGuiSelectableFlags, ImGuiCond,
+              ImGuiCol, ImGuiStyle, ImGuiTreeNodeFlags, ImGuiWindowFlags, ImVec2, ImVec4};
+pub use allocator::{allocator_stats, enable_rust_allocator, is_rust_allocator_enabled,
+                    AllocatorStats};
+pub use capture::{CapturedTexture, FrameCapture};
 pub use child_frame::ChildFrame;
+pub use clipboard::ClipboardBackend;
 pub use color_editors::{ColorButton, ColorEdit, ColorEditMode, ColorFormat, ColorPicker,
                         ColorPickerMode, ColorPreview, EditableColor};
+pub use fonts::{FontAtlas, FontConfig, FontGlyphRange, FontId, FontToken, SharedFontAtlas};
+pub use image::{Image, ImageButton};
 pub use input::{InputFloat, InputFloat2, InputFloat3, InputFloat4, InputInt, InputInt2, InputInt3,
                 InputInt4, InputText};
+pub use input_events::InputEvent;
+pub use keys::Key;
 pub use menus::{Menu, MenuItem};
+pub use mouse::{MouseButton, MouseCursor};
 pub use plothistogram::PlotHistogram;
 pub use plotlines::PlotLines;
 pub use progressbar::ProgressBar;
+pub use recording::{InputPlayer, InputRecord, InputRecording};
+pub use render::{DrawCallback, DrawCmd, DrawCmdIterator, DrawData, DrawList, DrawListIterator,
+                 OwnedDrawData, OwnedDrawList};
 pub use sliders::{SliderFloat, SliderFloat2, SliderFloat3, SliderFloat4, SliderInt, SliderInt2,
                   SliderInt3, SliderInt4};
+pub use settings::WindowSettings;
 pub use string::ImString;
 pub use style::StyleVar;
+pub use textures::{TextureId, Textures};
 pub use trees::{CollapsingHeader, TreeNode};
 pub use window::Window;
+pub use window_draw_list::{BezierCurve, ChannelsSplit, Circle, DrawListImage, Line, Path,
+                           PolyLine, Rect, Triangle, WindowDrawList};
 
+mod allocator;
+mod binary;
+mod capture;
 mod child_frame;
+mod clipboard;
 mod color_editors;
+mod fonts;
+mod image;
+mod ime;
 mod input;
+mod input_events;
+mod keys;
 mod menus;
+mod mouse;
 mod plothistogram;
 mod plotlines;
 mod progressbar;
+mod recording;
+mod render;
+mod settings;
 mod sliders;
 mod string;
 mod style;
+pub mod testing;
+mod textures;
 mod trees;
 mod window;
+mod window_draw_list;
 
 pub struct ImGui {
+    ctx: *mut sys::ImGuiContext,
+    font_atlas: SharedFontAtlas,
+    draw_callbacks: RefCell<Vec<DrawCallbackFn>>,
+    input_queue: InputQueue,
+    clipboard_ctx: Option<Box<ClipboardContext>>,
+    ime: Box<ImeCell>,
+    recording: Option<InputRecording>,
+    settings: RefCell<SettingsStore>,
+    // The UI of the frame that is being built, returned by Ui::current_ui
+    current_ui: UnsafeCell<Option<Ui<'static>>>,
     // We need to keep ownership of the ImStr values to ensure the *const char pointer
     // lives long enough in case the ImStr contains a Cow::Owned
     ini_filename: Option<ImString>,
@@ -68,43 +116,128 @@ pub fn get_version() -> &'static str {
 }
 
 impl ImGui {
-    pub fn init() -> ImGui {
-        ImGui {
+    /// Creates a new independent context with its own font atlas.
+    pub fn init() -> ImGui { ImGui::init_with_shared_font_atlas(&SharedFontAtlas::create()) }
+    /// Creates a new independent context that uses the given font atlas.
+    ///
+    /// Fonts added to the atlas through any of the contexts sharing it are visible to all of
+    /// them.
+    pub fn init_with_shared_font_atlas(font_atlas: &SharedFontAtlas) -> ImGui {
+        let imgui = ImGui {
+            ctx: allocator::create_context(),
+            font_atlas: font_atlas.clone(),
+            draw_callbacks: RefCell::new(Vec::new()),
+            input_queue: InputQueue::default(),
+            clipboard_ctx: None,
+            ime: Box::new(RefCell::new(ImeState::default())),
+            recording: None,
+            settings: RefCell::new(SettingsStore::default()),
+            current_ui: UnsafeCell::new(None),
             ini_filename: None,
             log_filename: None,
+        };
+        imgui.with_context(|| unsafe {
+            let io = &mut *sys::igGetIO();
+            io.fonts = imgui.font_atlas.raw();
+            io.ime_set_input_screen_pos_fn = Some(ime::set_input_screen_pos);
+            io.ime_window_handle = &*imgui.ime as *const ImeCell as *mut c_void;
+            for &(imgui_key, key) in Key::IMGUI_KEY_MAP.iter() {
+                io.key_map[imgui_key as usize] = key.index() as c_int;
+            }
+        });
+        imgui
+    }
+    /// Returns a handle to the font atlas used by this context, which can be used to create
+    /// other contexts sharing the same fonts.
+    pub fn shared_font_atlas(&self) -> SharedFontAtlas { self.font_atlas.clone() }
+    /// Runs a function with this context temporarily set as the current dear imgui context.
+    fn with_context<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        unsafe {
+            let prev_ctx = sys::igGetCurrentContext();
+            sys::igSetCurrentContext(self.ctx);
+            let result = f();
+            sys::igSetCurrentContext(prev_ctx);
+            result
         }
     }
-    fn io(&self) -> &sys::ImGuiIO { unsafe { &*sys::igGetIO() } }
-    fn io_mut(&mut self) -> &mut sys::ImGuiIO { unsafe { &mut *sys::igGetIO() } }
-    pub fn style(&self) -> &ImGuiStyle { unsafe { &*sys::igGetStyle() } }
-    pub fn style_mut(&mut self) -> &mut ImGuiStyle { unsafe { &mut *sys::igGetStyle() } }
+    /// Makes this context the current dear imgui context.
+    ///
+    /// Frames of several contexts can be built at the same time, so `Ui` and the widget
+    /// builders call this before calling into imgui.
+    #[inline]
+    pub(crate) fn make_current(&self) { unsafe { sys::igSetCurrentContext(self.ctx) } }
+    fn io(&self) -> &sys::ImGuiIO { unsafe { &*self.with_context(|| sys::igGetIO()) } }
+    fn io_mut(&mut self) -> &mut sys::ImGuiIO {
+        unsafe { &mut *self.with_context(|| sys::igGetIO()) }
+    }
+    /// Returns a builder for adding fonts to the font atlas.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// const ICON_RANGES: &[u16] = &[0xf000, 0xf3ff, 0];
+    ///
+    /// let config = FontConfig::new().size_pixels(16.0).oversample_h(2);
+    /// let text_font = imgui.fonts()
+    ///     .add_font_from_file("fonts/Roboto-Regular.ttf", &config)
+    ///     .unwrap();
+    /// let icon_config = config
+    ///     .merge_mode(true)
+    ///     .glyph_ranges(FontGlyphRange::Custom(ICON_RANGES));
+    /// imgui.fonts().add_font_from_file("fonts/icons.ttf", &icon_config).unwrap();
+    /// ```
+    pub fn fonts<'a>(&'a mut self) -> FontAtlas<'a> { FontAtlas::new(self) }
+    pub fn style(&self) -> &ImGuiStyle { unsafe { &*self.with_context(|| sys::igGetStyle()) } }
+    pub fn style_mut(&mut self) -> &mut ImGuiStyle {
+        unsafe { &mut *self.with_context(|| sys::igGetStyle()) }
+    }
     pub fn prepare_texture<'a, F, T>(&mut self, f: F) -> T
     where
         F: FnOnce(TextureHandle<'a>) -> T,
     {
-        let io = self.io();
+        let (pixels, width, height) = self.raw_font_texture_rgba32();
+        unsafe {
+            f(TextureHandle {
+                width: width,
+                height: height,
+                pixels: slice::from_raw_parts(pixels, width as usize * height as usize * 4),
+            })
+        }
+    }
+    /// Returns the size and RGBA pixels of the font atlas texture, building it if needed.
+    pub(crate) fn font_texture_rgba32(&self) -> (u32, u32, &[u8]) {
+        let (pixels, width, height) = self.raw_font_texture_rgba32();
+        let len = width as usize * height as usize * 4;
+        (width, height, unsafe { slice::from_raw_parts(pixels, len) })
+    }
+    fn raw_font_texture_rgba32(&self) -> (*const c_uchar, u32, u32) {
+        let atlas = self.font_atlas.raw();
         let mut pixels: *mut c_uchar = ptr::null_mut();
         let mut width: c_int = 0;
         let mut height: c_int = 0;
         let mut bytes_per_pixel: c_int = 0;
-        unsafe {
+        // Building the atlas allocates memory, so this context needs to be the current one
+        self.with_context(|| unsafe {
             sys::ImFontAtlas_GetTexDataAsRGBA32(
-                io.fonts,
+                atlas,
                 &mut pixels,
                 &mut width,
                 &mut height,
                 &mut bytes_per_pixel,
             );
-            f(TextureHandle {
-                width: width as u32,
-                height: height as u32,
-                pixels: slice::from_raw_parts(pixels, (width * height * bytes_per_pixel) as usize),
-            })
-        }
+        });
+        (pixels, width as u32, height as u32)
+    }
+    /// Returns the texture id of the font atlas texture.
+    pub fn font_texture_id(&self) -> TextureId {
+        unsafe { (*self.font_atlas.raw()).tex_id.into() }
     }
-    pub fn set_texture_id(&mut self, value: usize) {
+    /// Sets the texture id of the font atlas texture, which is passed to the renderer in draw
+    /// commands that use the font texture.
+    pub fn set_font_texture_id(&mut self, id: TextureId) {
         unsafe {
-            (*self.io_mut().fonts).tex_id = value as *mut c_void;
+            (*self.font_atlas.raw()).tex_id = id.as_raw();
         }
     }
     pub fn set_ini_filename(&mut self, value: Option<ImString>) {
@@ -117,6 +250,39 @@ impl ImGui {
         }
         self.ini_filename = value;
     }
+    /// Loads window settings in the `imgui.ini` format, e.g. a string returned by
+    /// `save_settings_to_string`. The settings are applied to each window the next time it's
+    /// built, replacing its position and size conditions except `ImGuiCond::Always`.
+    ///
+    /// These settings are kept by imgui-rs, separately from the settings of dear imgui itself:
+    ///
+    /// * Only windows built with `Ui::window` are tracked. Windows that dear imgui creates
+    ///   internally, e.g. by `Ui::show_test_window` or `Ui::show_metrics_window`, and windows
+    ///   begun through `imgui-sys` are neither saved nor restored.
+    /// * If `imgui.ini` is enabled, dear imgui restores and saves windows on its own, and the
+    ///   file can disagree with these settings. The ini file is usually disabled with
+    ///   `set_ini_filename(None)` when they are used.
+    /// * Every tracked window updates its settings in each frame it's built.
+    pub fn load_settings_from_str(&mut self, data: &str) {
+        self.apply_window_settings(&WindowSettings::parse(data));
+    }
+    /// Saves the settings of all windows built so far in the `imgui.ini` format. Windows built
+    /// with `Window::save_settings(false)` are not included, and neither are windows that
+    /// weren't built with `Ui::window` (see `load_settings_from_str`).
+    pub fn save_settings_to_string(&self) -> String {
+        WindowSettings::serialize(self.settings.borrow().windows())
+    }
+    /// Returns the settings of all windows built so far, in the order they first appeared.
+    pub fn window_settings(&self) -> Vec<WindowSettings> {
+        self.settings.borrow().windows().to_vec()
+    }
+    /// Applies window settings, e.g. a layout preset, to each window the next time it's built.
+    pub fn apply_window_settings(&mut self, windows: &[WindowSettings]) {
+        let mut settings = self.settings.borrow_mut();
+        for window in windows {
+            settings.apply(window);
+        }
+    }
     pub fn set_log_filename(&mut self, value: Option<ImString>) {
         {
             let io = self.io_mut();
@@ -171,55 +337,154 @@ impl ImGui {
         (io.mouse_pos.x, io.mouse_pos.y)
     }
     pub fn set_mouse_pos(&mut self, x: f32, y: f32) {
+        self.record(InputRecord::MousePos(x, y));
         let io = self.io_mut();
         io.mouse_pos.x = x;
         io.mouse_pos.y = y;
     }
     pub fn set_mouse_down(&mut self, states: &[bool; 5]) {
+        self.record(InputRecord::MouseDown(*states));
         let io = self.io_mut();
         io.mouse_down = *states;
     }
     pub fn set_mouse_wheel(&mut self, value: f32) {
+        self.record(InputRecord::MouseWheel(value));
         let io = self.io_mut();
         io.mouse_wheel = value;
     }
+    pub fn mouse_draw_cursor(&self) -> bool { self.io().mouse_draw_cursor }
     pub fn set_mouse_draw_cursor(&mut self, value: bool) {
         let io = self.io_mut();
         io.mouse_draw_cursor = value;
     }
+    pub fn key_ctrl(&self) -> bool { self.io().key_ctrl }
+    pub fn key_shift(&self) -> bool { self.io().key_shift }
+    pub fn key_alt(&self) -> bool { self.io().key_alt }
+    pub fn key_super(&self) -> bool { self.io().key_super }
     pub fn set_key_ctrl(&mut self, value: bool) {
+        self.record(InputRecord::KeyCtrl(value));
         let io = self.io_mut();
         io.key_ctrl = value;
     }
     pub fn set_key_shift(&mut self, value: bool) {
+        self.record(InputRecord::KeyShift(value));
         let io = self.io_mut();
         io.key_shift = value;
     }
     pub fn set_key_alt(&mut self, value: bool) {
+        self.record(InputRecord::KeyAlt(value));
         let io = self.io_mut();
         io.key_alt = value;
     }
     pub fn set_key_super(&mut self, value: bool) {
+        self.record(InputRecord::KeySuper(value));
         let io = self.io_mut();
         io.key_super = value;
     }
-    pub fn set_key(&mut self, key: u8, pressed: bool) {
+    /// Sets the state of a key. Modifier keys also update the ctrl/shift/alt/super state.
+    pub fn set_key(&mut self, key: Key, pressed: bool) {
+        self.record(InputRecord::Key(key, pressed));
         let io = self.io_mut();
-        io.keys_down[key as usize] = pressed;
-    }
-    pub fn set_imgui_key(&mut self, key: ImGuiKey, mapping: u8) {
-        let io = self.io_mut();
-        io.key_map[key as usize] = mapping as i32;
+        io.keys_down[key.index()] = pressed;
+        let down = io.keys_down;
+        let either = |a: Key, b: Key| down[a.index()] || down[b.index()];
+        match key {
+            Key::LeftCtrl | Key::RightCtrl => io.key_ctrl = either(Key::LeftCtrl, Key::RightCtrl),
+            Key::LeftShift | Key::RightShift => {
+                io.key_shift = either(Key::LeftShift, Key::RightShift)
+            }
+            Key::LeftAlt | Key::RightAlt => io.key_alt = either(Key::LeftAlt, Key::RightAlt),
+            Key::LeftSuper | Key::RightSuper => {
+                io.key_super = either(Key::LeftSuper, Key::RightSuper)
+            }
+            _ => (),
+        }
     }
     pub fn add_input_character(&mut self, character: char) {
+        self.record(InputRecord::Char(character));
         let mut buf = [0; 5];
-        unsafe {
+        self.with_context(|| unsafe {
             sys::ImGuiIO_AddInputCharactersUTF8(
                 sys::ImStr::from(character.encode_utf8(&mut buf) as &str));
+        });
+    }
+    /// Sets the system clipboard used by imgui for copying and pasting text, e.g. with Ctrl+C
+    /// and Ctrl+V in text inputs.
+    pub fn set_clipboard_backend(&mut self, backend: Box<ClipboardBackend>) {
+        let mut ctx = Box::new(ClipboardContext::new(backend));
+        // The box keeps the context at a stable address for as long as it's registered
+        let user_data = &mut *ctx as *mut ClipboardContext as *mut c_void;
+        {
+            let io = self.io_mut();
+            io.get_clipboard_text_fn = Some(clipboard::get_clipboard_text);
+            io.set_clipboard_text_fn = Some(clipboard::set_clipboard_text);
+            io.clipboard_user_data = user_data;
         }
+        self.clipboard_ctx = Some(ctx);
+    }
+    /// Sets a function that is called with the position of the text cursor in display
+    /// coordinates whenever it moves while a text input is active, so the platform can place the
+    /// IME candidate window next to it.
+    ///
+    /// The position is reported when the frame is rendered.
+    pub fn set_ime_position_handler<F: FnMut(f32, f32) + 'static>(&mut self, handler: F) {
+        self.ime.borrow_mut().set_handler(Box::new(handler));
+    }
+    /// Returns the last position reported to the IME position handler.
+    pub fn ime_position(&self) -> Option<(f32, f32)> { self.ime.borrow().pos() }
+    /// Sets the text the IME is currently composing. It's shown over the active text input until
+    /// it's cleared, and the committed characters should be added as usual with
+    /// `InputEvent::Char`.
+    pub fn set_ime_preedit(&mut self, text: &str) { self.ime.borrow_mut().set_preedit(text); }
+    /// Clears the text the IME is composing, e.g. when composition is committed or cancelled.
+    pub fn clear_ime_preedit(&mut self) { self.ime.borrow_mut().set_preedit(""); }
+    /// Returns the text the IME is currently composing.
+    pub fn ime_preedit(&self) -> String { self.ime.borrow().preedit().to_owned() }
+    /// Queues an input event. Queued events are applied when the next frame starts.
+    ///
+    /// Unlike setting the input state directly, no input is lost if it changes several times
+    /// between two frames: events that would hide the effect of an earlier event in the same
+    /// frame, such as a button press and release, are spread over several frames.
+    pub fn push_event(&mut self, event: InputEvent) { self.input_queue.push(event); }
+    /// Returns the number of queued input events that haven't been applied yet.
+    pub fn queued_event_count(&self) -> usize { self.input_queue.len() }
+    /// Discards all queued input events.
+    pub fn clear_events(&mut self) { self.input_queue.clear(); }
+    /// Starts recording input set through the `ImGui` setters and the sizes and delta times of
+    /// frames, replacing any recording in progress. The recording starts with the current mouse
+    /// and keyboard state so it can be replayed into a new context.
+    ///
+    /// Queued input events are recorded when they are applied.
+    pub fn start_recording(&mut self) {
+        let mut recording = InputRecording::new();
+        {
+            let io = self.io();
+            recording.push(InputRecord::MousePos(io.mouse_pos.x, io.mouse_pos.y));
+            recording.push(InputRecord::MouseDown(io.mouse_down));
+            for (index, &down) in io.keys_down.iter().enumerate() {
+                if let (true, Some(key)) = (down, Key::from_index(index)) {
+                    recording.push(InputRecord::Key(key, true));
+                }
+            }
+            recording.push(InputRecord::KeyCtrl(io.key_ctrl));
+            recording.push(InputRecord::KeyShift(io.key_shift));
+            recording.push(InputRecord::KeyAlt(io.key_alt));
+            recording.push(InputRecord::KeySuper(io.key_super));
+        }
+        self.recording = Some(recording);
+    }
+    /// Stops recording and returns the recording, if one was in progress.
+    pub fn stop_recording(&mut self) -> Option<InputRecording> { self.recording.take() }
+    pub fn is_recording(&self) -> bool { self.recording.is_some() }
+    fn record(&mut self, record: InputRecord) {
+        if let Some(ref mut recording) = self.recording {
+            recording.push(record);
+        }
+    }
+    pub fn get_time(&self) -> f32 { self.with_context(|| unsafe { sys::igGetTime() }) }
+    pub fn get_frame_count(&self) -> i32 {
+        self.with_context(|| unsafe { sys::igGetFrameCount() })
     }
-    pub fn get_time(&self) -> f32 { unsafe { sys::igGetTime() } }
-    pub fn get_frame_count(&self) -> i32 { unsafe { sys::igGetFrameCount() } }
     pub fn get_frame_rate(&self) -> f32 { self.io().framerate }
     pub fn frame<'ui, 'a: 'ui>(
         &'a mut self,
@@ -227,6 +492,14 @@ impl ImGui {
         size_pixels: (u32, u32),
         delta_time: f32,
     ) -> Ui<'ui> {
+        let mut input_queue = mem::replace(&mut self.input_queue, InputQueue::default());
+        input_queue.apply(self);
+        self.input_queue = input_queue;
+        self.record(InputRecord::Frame {
+            size_points: size_points,
+            size_pixels: size_pixels,
+            delta_time: delta_time,
+        });
         {
             let io = self.io_mut();
             io.display_size.x = size_points.0 as c_float;
@@ -243,9 +516,14 @@ impl ImGui {
             };
             io.delta_time = delta_time;
         }
+        // The draw lists of the previous frame are no longer used by the renderer
+        self.draw_callbacks.borrow_mut().clear();
         unsafe {
+            sys::igSetCurrentContext(self.ctx);
             sys::igNewFrame();
-            CURRENT_UI = Some(Ui { imgui: mem::transmute(self as &'a ImGui) });
+            // Ui::current_ui finds this context through the user data of its io
+            (*sys::igGetIO()).user_data = self as *mut ImGui as *mut c_void;
+            *self.current_ui.get() = Some(Ui { imgui: mem::transmute(self as &'a ImGui) });
         }
         Ui { imgui: self }
     }
@@ -254,20 +532,20 @@ impl ImGui {
 impl Drop for ImGui {
     fn drop(&mut self) {
         unsafe {
-            CURRENT_UI = None;
+            let prev_ctx = sys::igGetCurrentContext();
+            sys::igSetCurrentContext(self.ctx);
+            // igShutdown clears the font atlas, but it might still be used by other contexts.
+            // SharedFontAtlas takes care of clearing it once it's no longer used
+            (*sys::igGetIO()).fonts = ptr::null_mut();
             sys::igShutdown();
+            sys::igDestroyContext(self.ctx);
+            if prev_ctx != self.ctx {
+                sys::igSetCurrentContext(prev_ctx);
+            }
         }
     }
 }
 
-static mut CURRENT_UI: Option<Ui<'static>> = None;
-
-pub struct DrawList<'a> {
-    pub cmd_buffer: &'a [sys::ImDrawCmd],
-    pub idx_buffer: &'a [sys::ImDrawIdx],
-    pub vtx_buffer: &'a [sys::ImDrawVert],
-}
-
 pub struct Ui<'ui> {
     imgui: &'ui ImGui,
 }
@@ -290,6 +568,11 @@ impl<'ui> Ui<'ui> {
         let io = self.imgui.io();
         io.metrics_allocs
     }
+    /// Returns the number of bytes currently allocated by imgui, or `None` if imgui doesn't use
+    /// the Rust allocator (see `enable_rust_allocator`).
+    pub fn metrics_alloc_bytes(&self) -> Option<usize> {
+        allocator_stats().map(|stats| stats.live_bytes)
+    }
     pub fn metrics_render_vertices(&self) -> i32 {
         let io = self.imgui.io();
         io.metrics_render_vertices
@@ -302,39 +585,40 @@ impl<'ui> Ui<'ui> {
         let io = self.imgui.io();
         io.metrics_active_windows
     }
-    pub fn render<F, E>(self, mut f: F) -> Result<(), E>
-    where
-        F: FnMut(&Ui, DrawList) -> Result<(), E>,
-    {
+    /// Ends the frame and returns the draw data for the renderer.
+    ///
+    /// The draw data stays valid until the next frame is started.
+    pub fn render(self) -> DrawData<'ui> {
         unsafe {
+            sys::igSetCurrentContext(self.imgui.ctx);
             sys::igRender();
-
-            let draw_data = sys::igGetDrawData();
-            for &cmd_list in (*draw_data).cmd_lists() {
-                let draw_list = DrawList {
-                    cmd_buffer: (*cmd_list).cmd_buffer.as_slice(),
-                    idx_buffer: (*cmd_list).idx_buffer.as_slice(),
-                    vtx_buffer: (*cmd_list).vtx_buffer.as_slice(),
-                };
-                f(&self, draw_list)?;
-            }
-            CURRENT_UI = None;
+            *self.imgui.current_ui.get() = None;
+            (*sys::igGetIO()).user_data = ptr::null_mut();
+            DrawData::new(self.imgui, sys::igGetDrawData())
         }
-        Ok(())
     }
-    pub fn show_user_guide(&self) { unsafe { sys::igShowUserGuide() }; }
-    pub fn show_default_style_editor(&self) { unsafe { sys::igShowStyleEditor(ptr::null_mut()) }; }
+    pub fn show_user_guide(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igShowUserGuide() };
+    }
+    pub fn show_default_style_editor(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igShowStyleEditor(ptr::null_mut()) };
+    }
     pub fn show_style_editor<'p>(&self, style: &'p mut ImGuiStyle) {
+        self.imgui.make_current();
         unsafe {
             sys::igShowStyleEditor(style as *mut ImGuiStyle);
         }
     }
     pub fn show_test_window(&self, opened: &mut bool) {
+        self.imgui.make_current();
         unsafe {
             sys::igShowTestWindow(opened);
         }
     }
     pub fn show_metrics_window(&self, opened: &mut bool) {
+        self.imgui.make_current();
         unsafe {
             sys::igShowMetricsWindow(opened);
         }
@@ -342,7 +626,21 @@ impl<'ui> Ui<'ui> {
 }
 
 impl<'a> Ui<'a> {
-    pub unsafe fn current_ui() -> Option<&'a Ui<'a>> { CURRENT_UI.as_ref() }
+    /// Returns the UI of the frame that is being built in the current dear imgui context.
+    ///
+    /// Each context has its own current UI, and the context of a `Ui` is made current whenever
+    /// it's used.
+    pub unsafe fn current_ui() -> Option<&'a Ui<'a>> {
+        if sys::igGetCurrentContext().is_null() {
+            return None;
+        }
+        let imgui = (*sys::igGetIO()).user_data as *const ImGui;
+        if imgui.is_null() {
+            None
+        } else {
+            (*(*imgui).current_ui.get()).as_ref()
+        }
+    }
 }
 
 // Window
@@ -353,13 +651,19 @@ impl<'ui> Ui<'ui> {
 // Layout
 impl<'ui> Ui<'ui> {
     /// Pushes a value to the item width stack.
-    pub fn push_item_width(&self, width: f32) { unsafe { sys::igPushItemWidth(width) } }
+    pub fn push_item_width(&self, width: f32) {
+        self.imgui.make_current();
+        unsafe { sys::igPushItemWidth(width) }
+    }
 
     /// Pops a value from the item width stack.
     ///
     /// # Aborts
     /// The current process is aborted if the item width stack is empty.
-    pub fn pop_item_width(&self) { unsafe { sys::igPopItemWidth() } }
+    pub fn pop_item_width(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igPopItemWidth() }
+    }
 
     /// Runs a function after temporarily pushing a value to the item width stack.
     pub fn with_item_width<F>(&self, width: f32, f: F)
@@ -371,47 +675,148 @@ impl<'ui> Ui<'ui> {
         self.pop_item_width();
     }
 
-    pub fn separator(&self) { unsafe { sys::igSeparator() }; }
-    pub fn new_line(&self) { unsafe { sys::igNewLine() } }
-    pub fn same_line(&self, pos_x: f32) { unsafe { sys::igSameLine(pos_x, -1.0f32) } }
+    pub fn separator(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igSeparator() };
+    }
+    pub fn new_line(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igNewLine() }
+    }
+    pub fn same_line(&self, pos_x: f32) {
+        self.imgui.make_current();
+        unsafe { sys::igSameLine(pos_x, -1.0f32) }
+    }
     pub fn same_line_spacing(&self, pos_x: f32, spacing_w: f32) {
+        self.imgui.make_current();
         unsafe { sys::igSameLine(pos_x, spacing_w) }
     }
-    pub fn spacing(&self) { unsafe { sys::igSpacing() }; }
+    pub fn spacing(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igSpacing() };
+    }
+    /// Adds an empty item of the given size, e.g. to reserve space for custom drawing.
+    pub fn dummy<S: Into<ImVec2>>(&self, size: S) {
+        self.imgui.make_current();
+        let size = size.into();
+        unsafe { sys::igDummy(&size) }
+    }
 
     pub fn columns<'p>(&self, count: i32, id: &'p str, border: bool) {
+        self.imgui.make_current();
         unsafe { sys::igColumns(count, sys::ImStr::from(id), border) }
     }
 
-    pub fn next_column(&self) { unsafe { sys::igNextColumn() } }
+    pub fn next_column(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igNextColumn() }
+    }
 
-    pub fn get_column_index(&self) -> i32 { unsafe { sys::igGetColumnIndex() } }
+    pub fn get_column_index(&self) -> i32 {
+        self.imgui.make_current();
+        unsafe { sys::igGetColumnIndex() }
+    }
 
     pub fn get_column_offset(&self, column_index: i32) -> f32 {
+        self.imgui.make_current();
         unsafe { sys::igGetColumnOffset(column_index) }
     }
 
     pub fn set_column_offset(&self, column_index: i32, offset_x: f32) {
+        self.imgui.make_current();
         unsafe { sys::igSetColumnOffset(column_index, offset_x) }
     }
 
     pub fn get_column_width(&self, column_index: i32) -> f32 {
+        self.imgui.make_current();
         unsafe { sys::igGetColumnWidth(column_index) }
     }
 
-    pub fn get_columns_count(&self) -> i32 { unsafe { sys::igGetColumnsCount() } }
+    pub fn get_columns_count(&self) -> i32 {
+        self.imgui.make_current();
+        unsafe { sys::igGetColumnsCount() }
+    }
+
+    /// Returns the cursor position in absolute screen coordinates, which is where the next
+    /// widget is placed. This is useful for custom drawing with `get_window_draw_list`.
+    pub fn get_cursor_screen_pos(&self) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetCursorScreenPos(&mut out) };
+        (out.x, out.y)
+    }
+
+    /// Sets the cursor position in absolute screen coordinates.
+    pub fn set_cursor_screen_pos<P: Into<ImVec2>>(&self, pos: P) {
+        self.imgui.make_current();
+        unsafe { sys::igSetCursorScreenPos(pos.into()) }
+    }
+}
+
+// Fonts
+impl<'ui> Ui<'ui> {
+    /// Pushes a font to the font stack. The font is popped when the returned token is dropped.
+    ///
+    /// # Panics
+    /// Panics if the font is not in the font atlas.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// let monospace = imgui.fonts().add_default_font();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// let font = ui.push_font(monospace);
+    /// ui.text("[INFO] Started");
+    /// font.pop();
+    /// ```
+    pub fn push_font(&self, id: FontId) -> FontToken<'ui> {
+        self.imgui.make_current();
+        unsafe { sys::igPushFont(self.imgui.font_atlas.font(id)) };
+        FontToken::new(self)
+    }
+
+    /// Runs a function after temporarily pushing a font to the font stack.
+    ///
+    /// # Panics
+    /// Panics if the font is not in the font atlas.
+    pub fn with_font<F>(&self, id: FontId, f: F)
+    where
+        F: FnOnce(),
+    {
+        let _font = self.push_font(id);
+        f();
+    }
+
+    /// Returns the size of the current font in pixels, with the window font scale applied.
+    pub fn current_font_size(&self) -> f32 {
+        self.imgui.make_current();
+        unsafe { sys::igGetFontSize() }
+    }
+
+    /// Sets the font scale of the current window.
+    pub fn set_window_font_scale(&self, scale: f32) {
+        self.imgui.make_current();
+        unsafe { sys::igSetWindowFontScale(scale) }
+    }
 }
 
 // ID scopes
 impl<'ui> Ui<'ui> {
     /// Pushes an identifier to the ID stack.
-    pub fn push_id(&self, id: i32) { unsafe { sys::igPushIDInt(id) }; }
+    pub fn push_id(&self, id: i32) {
+        self.imgui.make_current();
+        unsafe { sys::igPushIDInt(id) };
+    }
 
     /// Pops an identifier from the ID stack.
     ///
     /// # Aborts
     /// The current process is aborted if the ID stack is empty.
-    pub fn pop_id(&self) { unsafe { sys::igPopID() }; }
+    pub fn pop_id(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igPopID() };
+    }
 
     /// Runs a function after temporarily pushing a value to the ID stack.
     pub fn with_id<F>(&self, id: i32, f: F)
@@ -427,6 +832,7 @@ impl<'ui> Ui<'ui> {
 // Widgets
 impl<'ui> Ui<'ui> {
     pub fn text<'p>(&self, text: &'p str) {
+        self.imgui.make_current();
         unsafe {
             sys::igTextUnformatted1(sys::ImStr::from(text));
         }
@@ -435,45 +841,55 @@ impl<'ui> Ui<'ui> {
     where
         A: Into<ImVec4>,
     {
+        self.imgui.make_current();
         unsafe {
             sys::igTextColored1(col.into(), sys::ImStr::from(text));
         }
     }
     pub fn text_disabled<'p>(&self, text: &'p str) {
+        self.imgui.make_current();
         unsafe {
             sys::igTextDisabled1(sys::ImStr::from(text));
         }
     }
     pub fn text_wrapped<'p>(&self, text: &'p str) {
+        self.imgui.make_current();
         unsafe {
             sys::igTextWrapped1(sys::ImStr::from(text));
         }
     }
     pub fn label_text<'p>(&self, label: &'p str, text: &'p str) {
+        self.imgui.make_current();
         unsafe {
             sys::igLabelText1(sys::ImStr::from(label), sys::ImStr::from(text));
         }
     }
     pub fn bullet(&self) {
+        self.imgui.make_current();
         unsafe {
             sys::igBullet();
         }
     }
     pub fn bullet_text<'p>(&self, text: &'p str) {
+        self.imgui.make_current();
         unsafe {
             sys::igBulletText1(sys::ImStr::from(text));
         }
     }
     pub fn button<'p>(&self, label: &'p str) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igButton(sys::ImStr::from(label), ImVec2::zero()) }
     }
     pub fn button_with_size<'p, S: Into<ImVec2>>(&self, label: &'p str, size: S) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igButton(sys::ImStr::from(label), size.into()) }
     }
     pub fn small_button<'p>(&self, label: &'p str) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igSmallButton(sys::ImStr::from(label)) }
     }
     pub fn checkbox<'p>(&self, label: &'p str, value: &'p mut bool) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igCheckbox(sys::ImStr::from(label), value) }
     }
 }
@@ -627,6 +1043,7 @@ impl<'ui> Ui<'ui> {
     /// default format, picker type, etc. Users will be able to change many settings, unless you
     /// use .options(false) in your widget builders.
     pub fn set_color_edit_options(&self, flags: ImGuiColorEditFlags) {
+        self.imgui.make_current();
         unsafe {
             sys::igSetColorEditOptions(flags);
         }
@@ -650,6 +1067,7 @@ impl<'ui> Ui<'ui> {
         flags: ImGuiSelectableFlags,
         size: S,
     ) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igSelectable(sys::ImStr::from(label), selected, flags, size.into()) }
     }
 }
@@ -677,8 +1095,10 @@ impl<'ui> Ui<'ui> {
     /// # }
     /// ```
     pub fn tooltip<F: FnOnce()>(&self, f: F) {
+        self.imgui.make_current();
         unsafe { sys::igBeginTooltip() };
         f();
+        self.imgui.make_current();
         unsafe { sys::igEndTooltip() };
     }
     /// Construct a tooltip window with simple text content.
@@ -708,9 +1128,11 @@ impl<'ui> Ui<'ui> {
     where
         F: FnOnce(),
     {
+        self.imgui.make_current();
         let render = unsafe { sys::igBeginMainMenuBar() };
         if render {
             f();
+            self.imgui.make_current();
             unsafe { sys::igEndMainMenuBar() };
         }
     }
@@ -718,9 +1140,11 @@ impl<'ui> Ui<'ui> {
     where
         F: FnOnce(),
     {
+        self.imgui.make_current();
         let render = unsafe { sys::igBeginMenuBar() };
         if render {
             f();
+            self.imgui.make_current();
             unsafe { sys::igEndMenuBar() };
         }
     }
@@ -733,19 +1157,25 @@ impl<'ui> Ui<'ui> {
 // Widgets: Popups
 impl<'ui> Ui<'ui> {
     pub fn open_popup<'p>(&self, str_id: &'p str) {
+        self.imgui.make_current();
         unsafe { sys::igOpenPopup(sys::ImStr::from(str_id)) };
     }
     pub fn popup<'p, F>(&self, str_id: &'p str, f: F)
     where
         F: FnOnce(),
     {
+        self.imgui.make_current();
         let render = unsafe { sys::igBeginPopup(sys::ImStr::from(str_id)) };
         if render {
             f();
+            self.imgui.make_current();
             unsafe { sys::igEndPopup() };
         }
     }
-    pub fn close_current_popup(&self) { unsafe { sys::igCloseCurrentPopup() }; }
+    pub fn close_current_popup(&self) {
+        self.imgui.make_current();
+        unsafe { sys::igCloseCurrentPopup() };
+    }
 }
 
 // Widgets: Combos
@@ -757,6 +1187,7 @@ impl<'ui> Ui<'ui> {
         items: &'p [&'p str],
         height_in_items: i32,
     ) -> bool {
+        self.imgui.make_current();
         let items_inner: Vec<sys::ImStr> = items.into_iter().map(|item| sys::ImStr::from(*item)).collect();
         unsafe {
             sys::igCombo(
@@ -779,6 +1210,7 @@ impl<'ui> Ui<'ui> {
         items: &'p [&'p str],
         height_in_items: i32,
     ) -> bool {
+        self.imgui.make_current();
         let items_inner: Vec<sys::ImStr> = items.into_iter().map(|item| sys::ImStr::from(*item)).collect();
         unsafe {
             sys::igListBox(
@@ -808,6 +1240,7 @@ impl<'ui> Ui<'ui> {
     /// ui.radio_button("Item 3", &mut selected_radio_value, 3);
     /// ```
     pub fn radio_button<'p>(&self, label: &'p str, value: &'p mut i32, wanted: i32) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igRadioButton(sys::ImStr::from(label), value, wanted) }
     }
 
@@ -828,6 +1261,7 @@ impl<'ui> Ui<'ui> {
     /// }
     /// ```
     pub fn radio_button_bool<'p>(&self, label: &'p str, value: bool) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igRadioButtonBool(sys::ImStr::from(label), value) }
     }
 }
@@ -861,6 +1295,7 @@ impl<'ui> Ui<'ui> {
         hide_text_after_double_hash: bool,
         wrap_width: f32,
     ) -> ImVec2 {
+        self.imgui.make_current();
         let mut buffer = ImVec2::new(0.0, 0.0);
         unsafe {
             sys::igCalcTextSize(
@@ -892,6 +1327,51 @@ impl<'ui> Ui<'ui> {
     }
 }
 
+// Widgets: Images
+impl<'ui> Ui<'ui> {
+    /// Creates an image widget, which draws a texture registered with the renderer.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// # let texture_id = TextureId::new(1);
+    /// ui.image(texture_id, (64.0, 64.0))
+    ///     .uv0((0.0, 0.0))
+    ///     .uv1((0.5, 0.5))
+    ///     .border_col((1.0, 1.0, 1.0, 1.0))
+    ///     .build();
+    /// ```
+    pub fn image<S: Into<ImVec2>>(&self, texture_id: TextureId, size: S) -> Image<'ui> {
+        Image::new(self, texture_id, size)
+    }
+    /// Creates an image button, which draws a texture registered with the renderer inside a
+    /// button frame. `build` returns true if the button was clicked.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// # let texture_id = TextureId::new(1);
+    /// if ui.image_button(texture_id, (32.0, 32.0))
+    ///     .frame_padding(2)
+    ///     .bg_col((0.0, 0.0, 0.0, 1.0))
+    ///     .build()
+    /// {
+    ///     println!("Clicked!");
+    /// }
+    /// ```
+    pub fn image_button<S: Into<ImVec2>>(
+        &self,
+        texture_id: TextureId,
+        size: S,
+    ) -> ImageButton<'ui> {
+        ImageButton::new(self, texture_id, size)
+    }
+}
+
 impl<'ui> Ui<'ui> {
     /// Creates a child frame. Size is size of child_frame within parent window.
     ///
@@ -937,6 +1417,7 @@ impl<'ui> Ui<'ui> {
     pub fn with_style_var<F: FnOnce()>(&self, style_var: StyleVar, f: F) {
         self.push_style_var(style_var);
         f();
+        self.imgui.make_current();
         unsafe { sys::igPopStyleVar(1) }
     }
 
@@ -961,6 +1442,7 @@ impl<'ui> Ui<'ui> {
             self.push_style_var(style_var);
         }
         f();
+        self.imgui.make_current();
         unsafe { sys::igPopStyleVar(style_vars.len() as i32) };
     }
 
@@ -968,6 +1450,7 @@ impl<'ui> Ui<'ui> {
     fn push_style_var(&self, style_var: StyleVar) {
         use StyleVar::*;
         use sys::{igPushStyleVar, igPushStyleVarVec};
+        self.imgui.make_current();
         match style_var {
             Alpha(v) => unsafe { igPushStyleVar(ImGuiStyleVar::Alpha, v) },
             WindowPadding(v) => unsafe { igPushStyleVarVec(ImGuiStyleVar::WindowPadding, v) },
@@ -1005,10 +1488,12 @@ impl<'ui> Ui<'ui> {
         color: C,
         f: F,
     ) {
+        self.imgui.make_current();
         unsafe {
             sys::igPushStyleColor(var, color.into());
         }
         f();
+        self.imgui.make_current();
         unsafe {
             sys::igPopStyleColor(1);
         }
@@ -1033,16 +1518,205 @@ impl<'ui> Ui<'ui> {
         color_vars: &[(ImGuiCol, C)],
         f: F,
     ) {
+        self.imgui.make_current();
         for &(color_var, color) in color_vars {
             unsafe {
                 sys::igPushStyleColor(color_var, color.into());
             }
         }
         f();
+        self.imgui.make_current();
         unsafe { sys::igPopStyleColor(color_vars.len() as i32) };
     }
 }
 
+// Custom drawing
+impl<'ui> Ui<'ui> {
+    /// Returns the draw list of the current window, for drawing custom shapes.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// let (x, y) = ui.get_cursor_screen_pos();
+    /// let draw_list = ui.get_window_draw_list();
+    /// draw_list
+    ///     .add_rect((x, y), (x + 100.0, y + 20.0), [0.2, 0.2, 0.2])
+    ///     .rounding(4.0)
+    ///     .filled(true)
+    ///     .build();
+    /// draw_list
+    ///     .add_line((x, y + 10.0), (x + 100.0, y + 10.0), [1.0, 0.0, 0.0])
+    ///     .thickness(2.0)
+    ///     .build();
+    /// draw_list.add_circle((x + 50.0, y + 10.0), 8.0, [0.0, 1.0, 0.0]).build();
+    /// ```
+    pub fn get_window_draw_list(&self) -> WindowDrawList<'ui> { WindowDrawList::new(self) }
+}
+
+// Keyboard
+impl<'ui> Ui<'ui> {
+    /// Returns true if the key is being held down.
+    pub fn is_key_down(&self, key: Key) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsKeyDown(key.index() as c_int) }
+    }
+    /// Returns true if the key was pressed this frame. If `repeat` is true, this also returns
+    /// true at the key repeat rate while the key is held down.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// if ui.imgui().key_ctrl() && ui.is_key_pressed(Key::S, false) {
+    ///     println!("Save");
+    /// }
+    /// ```
+    pub fn is_key_pressed(&self, key: Key, repeat: bool) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsKeyPressed(key.index() as c_int, repeat) }
+    }
+    /// Returns true if the key was released this frame.
+    pub fn is_key_released(&self, key: Key) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsKeyReleased(key.index() as c_int) }
+    }
+    /// Returns how many times the key was repeated this frame with the given repeat delay and
+    /// rate, which is usually 0 or 1.
+    pub fn key_pressed_amount(&self, key: Key, repeat_delay: f32, rate: f32) -> u32 {
+        self.imgui.make_current();
+        unsafe { sys::igGetKeyPressedAmount(key.index() as c_int, repeat_delay, rate) as u32 }
+    }
+}
+
+// Mouse
+impl<'ui> Ui<'ui> {
+    /// Returns true if the mouse button is being held down.
+    pub fn is_mouse_down(&self, button: MouseButton) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsMouseDown(button.index() as c_int) }
+    }
+    /// Returns true if the mouse button was clicked this frame.
+    pub fn is_mouse_clicked(&self, button: MouseButton) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsMouseClicked(button.index() as c_int, false) }
+    }
+    /// Returns true if the mouse button was double-clicked this frame.
+    pub fn is_mouse_double_clicked(&self, button: MouseButton) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsMouseDoubleClicked(button.index() as c_int) }
+    }
+    /// Returns true if the mouse button was released this frame.
+    pub fn is_mouse_released(&self, button: MouseButton) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsMouseReleased(button.index() as c_int) }
+    }
+    /// Returns true if the mouse is being dragged with the button held down, further than the
+    /// drag threshold of the io settings.
+    pub fn is_mouse_dragging(&self, button: MouseButton) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsMouseDragging(button.index() as c_int, -1.0) }
+    }
+    /// Returns true if the mouse is being dragged with the button held down, further than the
+    /// given distance in pixels.
+    pub fn is_mouse_dragging_with_threshold(&self, button: MouseButton, threshold: f32) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsMouseDragging(button.index() as c_int, threshold) }
+    }
+    /// Returns the distance the mouse has been dragged since the button was pressed, or
+    /// `(0.0, 0.0)` until the drag threshold of the io settings is reached.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// # let mut scroll = (0.0, 0.0);
+    /// if ui.is_mouse_dragging(MouseButton::Middle) {
+    ///     let delta = ui.mouse_drag_delta(MouseButton::Middle);
+    ///     scroll.0 += delta.0;
+    ///     scroll.1 += delta.1;
+    ///     ui.reset_mouse_drag_delta(MouseButton::Middle);
+    /// }
+    /// ```
+    pub fn mouse_drag_delta(&self, button: MouseButton) -> (f32, f32) {
+        self.mouse_drag_delta_with_threshold(button, -1.0)
+    }
+    /// Returns the distance the mouse has been dragged since the button was pressed, or
+    /// `(0.0, 0.0)` until it has moved further than the given distance in pixels.
+    pub fn mouse_drag_delta_with_threshold(
+        &self,
+        button: MouseButton,
+        threshold: f32,
+    ) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetMouseDragDelta(&mut out, button.index() as c_int, threshold) };
+        (out.x, out.y)
+    }
+    /// Resets the drag delta of the button, so the next delta is relative to the current mouse
+    /// position.
+    pub fn reset_mouse_drag_delta(&self, button: MouseButton) {
+        self.imgui.make_current();
+        unsafe { sys::igResetMouseDragDelta(button.index() as c_int) }
+    }
+    /// Returns true if the mouse is over the given rectangle. If `clip` is true, the rectangle
+    /// is clipped to the current clip rectangle first.
+    pub fn is_mouse_hovering_rect<P1, P2>(&self, r_min: P1, r_max: P2, clip: bool) -> bool
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+    {
+        self.imgui.make_current();
+        unsafe { sys::igIsMouseHoveringRect(r_min.into(), r_max.into(), clip) }
+    }
+    /// Returns the mouse cursor shape imgui wants to show this frame, or `None` if the cursor
+    /// should be hidden.
+    ///
+    /// Platform code should apply this to the hardware cursor after building the frame, unless
+    /// imgui draws the cursor itself (`ImGui::mouse_draw_cursor`).
+    pub fn mouse_cursor(&self) -> Option<MouseCursor> {
+        self.imgui.make_current();
+        MouseCursor::from_raw(unsafe { sys::igGetMouseCursor() })
+    }
+    /// Sets the mouse cursor shape for this frame, or hides the cursor with `None`.
+    ///
+    /// imgui resets the cursor to `MouseCursor::Arrow` at the start of every frame.
+    pub fn set_mouse_cursor(&self, cursor: Option<MouseCursor>) {
+        self.imgui.make_current();
+        unsafe { sys::igSetMouseCursor(MouseCursor::to_raw(cursor)) }
+    }
+    /// Returns the mouse position from when the current popup was opened.
+    pub fn mouse_pos_on_opening_current_popup(&self) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetMousePosOnOpeningCurrentPopup(&mut out) };
+        (out.x, out.y)
+    }
+}
+
+// Clipboard
+impl<'ui> Ui<'ui> {
+    /// Returns the clipboard text, or `None` if the clipboard is empty.
+    pub fn clipboard_text(&self) -> Option<String> {
+        self.imgui.make_current();
+        let text = unsafe { sys::igGetClipboardText() };
+        if text.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(text) }.to_string_lossy().into_owned())
+        }
+    }
+    /// Sets the clipboard text. The text is truncated at the first nul character.
+    pub fn set_clipboard_text(&self, text: &str) {
+        self.imgui.make_current();
+        let text = clipboard::to_cstring(text);
+        unsafe { sys::igSetClipboardText(text.as_ptr()) }
+    }
+}
+
 /// # Utilities
 impl<'ui> Ui<'ui> {
     /// Returns `true` if the last item is being hovered by the mouse.
@@ -1060,6 +1734,136 @@ impl<'ui> Ui<'ui> {
     /// # }
     /// ```
     pub fn is_item_hovered(&self) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igIsItemHovered(ImGuiHoveredFlags::empty()) }
     }
+    /// Returns `true` if the last item is active, e.g. a button being held down or a text input
+    /// being edited.
+    pub fn is_item_active(&self) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsItemActive() }
+    }
+    /// Returns `true` if the last item was clicked with the given mouse button this frame.
+    pub fn is_item_clicked(&self, button: MouseButton) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsItemClicked(button.index() as c_int) }
+    }
+    /// Returns `true` if the last item is not clipped away.
+    pub fn is_item_visible(&self) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsItemVisible() }
+    }
+    /// Returns `true` if any item is being hovered by the mouse.
+    pub fn is_any_item_hovered(&self) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsAnyItemHovered() }
+    }
+    /// Returns `true` if any item is active.
+    pub fn is_any_item_active(&self) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsAnyItemActive() }
+    }
+    /// Returns the upper-left corner of the last item's bounding rectangle in screen
+    /// coordinates.
+    pub fn get_item_rect_min(&self) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetItemRectMin(&mut out) };
+        (out.x, out.y)
+    }
+    /// Returns the lower-right corner of the last item's bounding rectangle in screen
+    /// coordinates.
+    pub fn get_item_rect_max(&self) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetItemRectMax(&mut out) };
+        (out.x, out.y)
+    }
+    /// Returns the size of the last item's bounding rectangle.
+    pub fn get_item_rect_size(&self) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetItemRectSize(&mut out) };
+        (out.x, out.y)
+    }
+    /// Returns the position of the current window in screen coordinates.
+    pub fn get_window_pos(&self) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetWindowPos(&mut out) };
+        (out.x, out.y)
+    }
+    /// Returns the size of the current window.
+    pub fn get_window_size(&self) -> (f32, f32) {
+        self.imgui.make_current();
+        let mut out = ImVec2::new(0.0, 0.0);
+        unsafe { sys::igGetWindowSize(&mut out) };
+        (out.x, out.y)
+    }
+    /// Returns `true` if the current window is collapsed.
+    pub fn is_window_collapsed(&self) -> bool {
+        self.imgui.make_current();
+        unsafe { sys::igIsWindowCollapsed() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_context() -> ImGui {
+        let mut imgui = ImGui::init();
+        imgui.set_ini_filename(None);
+        imgui.prepare_texture(|_| ());
+        imgui
+    }
+
+    #[test]
+    fn interleaved_contexts() {
+        let mut a = test_context();
+        let mut b = test_context();
+        let (a_ptr, b_ptr) = (&a as *const ImGui, &b as *const ImGui);
+        // New windows are hidden in their first frame, so the draw lists of the second frame
+        // are checked
+        for frame in 0..2 {
+            let ui_a = a.frame((640, 480), (640, 480), 1.0 / 60.0);
+            let ui_b = b.frame((640, 480), (640, 480), 1.0 / 60.0);
+            ui_a.window("A").build(|| {
+                let draw_list_a = ui_a.get_window_draw_list();
+                let path_a = draw_list_a.path().line_to((10.0, 10.0));
+                ui_b.window("B1").build(|| {
+                    ui_b.text("B1");
+                    // Draw lists of both contexts are used while the other one is current
+                    let draw_list_b = ui_b.get_window_draw_list();
+                    draw_list_b.channels_split(2, |channels| {
+                        draw_list_a.add_line((0.0, 0.0), (10.0, 10.0), [1.0, 0.0, 0.0]).build();
+                        channels.set_current(1);
+                        draw_list_b.add_rect((0.0, 0.0), (10.0, 10.0), [0.0, 1.0, 0.0]).build();
+                        draw_list_a.with_clip_rect((0.0, 0.0), (20.0, 20.0), || {
+                            draw_list_b.add_circle((5.0, 5.0), 5.0, [0.0, 0.0, 1.0]).build();
+                        });
+                    });
+                });
+                assert!(ptr::eq(unsafe { Ui::current_ui() }.unwrap().imgui, b_ptr));
+                path_a.line_to((20.0, 10.0)).line_to((20.0, 20.0)).fill([1.0, 1.0, 0.0]);
+                ui_a.text("A");
+                assert!(ptr::eq(unsafe { Ui::current_ui() }.unwrap().imgui, a_ptr));
+            });
+            ui_b.window("B2").build(|| {
+                ui_b.text("B2");
+                let draw_list = ui_b.get_window_draw_list();
+                draw_list.path().rect((0.0, 0.0), (5.0, 5.0), 1.0).stroke([1.0, 1.0, 1.0], true, 1.0);
+            });
+            let a_lists = ui_a.render().draw_list_count();
+            assert!(unsafe { Ui::current_ui() }.is_none());
+            // Rendering one context doesn't end the frame of the other one
+            ui_b.is_item_hovered();
+            assert!(ptr::eq(unsafe { Ui::current_ui() }.unwrap().imgui, b_ptr));
+            let b_lists = ui_b.render().draw_list_count();
+            if frame == 1 {
+                assert_eq!(a_lists, 1);
+                assert_eq!(b_lists, 2);
+            }
+        }
+    }
 }
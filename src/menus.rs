@@ -1,22 +1,21 @@
 use sys;
-use std::marker::PhantomData;
 use std::ptr;
 
-use super::Ui;
+use super::{ImGui, Ui};
 
 #[must_use]
 pub struct Menu<'ui, 'p> {
     label: &'p str,
     enabled: bool,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> Menu<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str) -> Self {
         Menu {
             label: label,
             enabled: true,
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -25,9 +24,11 @@ impl<'ui, 'p> Menu<'ui, 'p> {
         self
     }
     pub fn build<F: FnOnce()>(self, f: F) {
+        self.imgui.make_current();
         let render = unsafe { sys::igBeginMenu(sys::ImStr::from(self.label), self.enabled) };
         if render {
             f();
+            self.imgui.make_current();
             unsafe { sys::igEndMenu() };
         }
     }
@@ -39,17 +40,17 @@ pub struct MenuItem<'ui, 'p> {
     shortcut: Option<&'p str>,
     selected: Option<&'p mut bool>,
     enabled: bool,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> MenuItem<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str) -> Self {
         MenuItem {
             label: label,
             shortcut: None,
             selected: None,
             enabled: true,
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -68,6 +69,7 @@ impl<'ui, 'p> MenuItem<'ui, 'p> {
         self
     }
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         let label = sys::ImStr::from(self.label);
         let shortcut = self.shortcut.map(|x| sys::ImStr::from(x)).unwrap_or(sys::ImStr::null());
         let selected = self.selected.map(|x| x as *mut bool).unwrap_or(
@@ -0,0 +1,64 @@
+use sys::ImGuiMouseCursor;
+
+/// A mouse button.
+///
+/// The discriminant is the index of the button in `ImGuiIO::mouse_down`.
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Extra1,
+    Extra2,
+}
+
+impl MouseButton {
+    /// Returns the index of the button in `ImGuiIO::mouse_down`.
+    pub fn index(self) -> usize { self as usize }
+}
+
+/// A mouse cursor shape requested by imgui.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseCursor {
+    Arrow,
+    /// When hovering over text inputs
+    TextInput,
+    /// Unused by imgui itself
+    Move,
+    /// When hovering over a horizontal border
+    ResizeNS,
+    /// When hovering over a vertical border or a column
+    ResizeEW,
+    /// When hovering over the bottom left corner of a window
+    ResizeNESW,
+    /// When hovering over the bottom right corner of a window
+    ResizeNWSE,
+}
+
+impl MouseCursor {
+    pub(crate) fn from_raw(raw: ImGuiMouseCursor) -> Option<MouseCursor> {
+        match raw {
+            ImGuiMouseCursor::None => None,
+            ImGuiMouseCursor::Arrow => Some(MouseCursor::Arrow),
+            ImGuiMouseCursor::TextInput => Some(MouseCursor::TextInput),
+            ImGuiMouseCursor::Move => Some(MouseCursor::Move),
+            ImGuiMouseCursor::ResizeNS => Some(MouseCursor::ResizeNS),
+            ImGuiMouseCursor::ResizeEW => Some(MouseCursor::ResizeEW),
+            ImGuiMouseCursor::ResizeNESW => Some(MouseCursor::ResizeNESW),
+            ImGuiMouseCursor::ResizeNWSE => Some(MouseCursor::ResizeNWSE),
+        }
+    }
+    pub(crate) fn to_raw(cursor: Option<MouseCursor>) -> ImGuiMouseCursor {
+        match cursor {
+            None => ImGuiMouseCursor::None,
+            Some(MouseCursor::Arrow) => ImGuiMouseCursor::Arrow,
+            Some(MouseCursor::TextInput) => ImGuiMouseCursor::TextInput,
+            Some(MouseCursor::Move) => ImGuiMouseCursor::Move,
+            Some(MouseCursor::ResizeNS) => ImGuiMouseCursor::ResizeNS,
+            Some(MouseCursor::ResizeEW) => ImGuiMouseCursor::ResizeEW,
+            Some(MouseCursor::ResizeNESW) => ImGuiMouseCursor::ResizeNESW,
+            Some(MouseCursor::ResizeNWSE) => ImGuiMouseCursor::ResizeNWSE,
+        }
+    }
+}
@@ -1,9 +1,8 @@
 use sys;
 use std::{f32, mem};
-use std::marker::PhantomData;
 use std::os::raw::c_float;
 
-use super::{ImVec2, Ui};
+use super::{ImGui, ImVec2, Ui};
 
 #[must_use]
 pub struct PlotLines<'ui, 'p> {
@@ -14,11 +13,11 @@ pub struct PlotLines<'ui, 'p> {
     scale_min: f32,
     scale_max: f32,
     graph_size: ImVec2,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> PlotLines<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str, values: &'p [f32]) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, values: &'p [f32]) -> Self {
         PlotLines {
             label: label,
             values: values,
@@ -27,7 +26,7 @@ impl<'ui, 'p> PlotLines<'ui, 'p> {
             scale_min: f32::MAX,
             scale_max: f32::MAX,
             graph_size: ImVec2::new(0.0f32, 0.0f32),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
 
@@ -62,6 +61,7 @@ impl<'ui, 'p> PlotLines<'ui, 'p> {
     }
 
     pub fn build(self) {
+        self.imgui.make_current();
         unsafe {
             sys::igPlotLines(
                 sys::ImStr::from(self.label),
@@ -1,8 +1,7 @@
 #![warn(missing_docs)]
 use sys;
-use std::marker::PhantomData;
 
-use super::{ImVec2, Ui};
+use super::{ImGui, ImVec2, Ui};
 
 /// Progress bar widget.
 #[must_use]
@@ -10,7 +9,7 @@ pub struct ProgressBar<'ui, 'p> {
     fraction: f32,
     size: ImVec2,
     overlay_text: Option<&'p str>,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> ProgressBar<'ui, 'p> {
@@ -19,12 +18,12 @@ impl<'ui, 'p> ProgressBar<'ui, 'p> {
     /// The progress bar will be automatically sized to fill
     /// the entire width of the window if no custom size is
     /// specified.
-    pub fn new(_: &Ui<'ui>, fraction: f32) -> Self {
+    pub fn new(ui: &Ui<'ui>, fraction: f32) -> Self {
         ProgressBar {
             fraction: fraction,
             size: ImVec2::new(-1.0, 0.0),
             overlay_text: None,
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
 
@@ -47,6 +46,7 @@ impl<'ui, 'p> ProgressBar<'ui, 'p> {
     /// Builds the progress bar. This has to be called after setting all parameters
     /// of the progress bar, otherwise the it will not be shown.
     pub fn build(self) {
+        self.imgui.make_current();
         unsafe {
             sys::igProgressBar(
                 self.fraction,
@@ -0,0 +1,400 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use binary::{invalid_data, read_bool, read_f32, read_u32, read_u8, write_f32, write_u32};
+use super::{ImGui, Key, Ui};
+
+/// One input change or frame captured by `ImGui::start_recording`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputRecord {
+    /// `ImGui::frame` was called with the given sizes and delta time
+    Frame {
+        size_points: (u32, u32),
+        size_pixels: (u32, u32),
+        delta_time: f32,
+    },
+    MousePos(f32, f32),
+    MouseDown([bool; 5]),
+    MouseWheel(f32),
+    KeyCtrl(bool),
+    KeyShift(bool),
+    KeyAlt(bool),
+    KeySuper(bool),
+    Key(Key, bool),
+    Char(char),
+}
+
+const MAGIC: &'static [u8; 8] = b"IMGUIREC";
+const VERSION: u8 = 1;
+
+const TAG_FRAME: u8 = 0;
+// A frame with the same sizes and delta time as the previous one
+const TAG_REPEAT_FRAME: u8 = 1;
+const TAG_MOUSE_POS: u8 = 2;
+const TAG_MOUSE_DOWN: u8 = 3;
+const TAG_MOUSE_WHEEL: u8 = 4;
+const TAG_KEY_CTRL: u8 = 5;
+const TAG_KEY_SHIFT: u8 = 6;
+const TAG_KEY_ALT: u8 = 7;
+const TAG_KEY_SUPER: u8 = 8;
+const TAG_KEY: u8 = 9;
+const TAG_CHAR: u8 = 10;
+
+/// A recorded stream of input and frames, which can be replayed with `InputPlayer` to reproduce
+/// the same UI state.
+///
+/// Recordings are saved in a compact binary format: an 8-byte magic and a version byte,
+/// followed by one tag byte per record and its little-endian payload. Runs of frames with the
+/// same sizes and delta time take one byte per frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputRecording {
+    records: Vec<InputRecord>,
+}
+
+impl InputRecording {
+    pub fn new() -> InputRecording { InputRecording::default() }
+    pub fn records(&self) -> &[InputRecord] { &self.records }
+    pub fn push(&mut self, record: InputRecord) { self.records.push(record); }
+    /// Returns the number of recorded frames.
+    pub fn frame_count(&self) -> usize {
+        self.records
+            .iter()
+            .filter(|record| match **record {
+                InputRecord::Frame { .. } => true,
+                _ => false,
+            })
+            .count()
+    }
+
+    /// Writes the recording in the binary recording format.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+        w.write_all(&[VERSION])?;
+        let mut last_frame = None;
+        for record in &self.records {
+            match *record {
+                InputRecord::Frame { .. } if last_frame == Some(*record) => {
+                    w.write_all(&[TAG_REPEAT_FRAME])?;
+                }
+                InputRecord::Frame {
+                    size_points,
+                    size_pixels,
+                    delta_time,
+                } => {
+                    w.write_all(&[TAG_FRAME])?;
+                    write_u32(w, size_points.0)?;
+                    write_u32(w, size_points.1)?;
+                    write_u32(w, size_pixels.0)?;
+                    write_u32(w, size_pixels.1)?;
+                    write_f32(w, delta_time)?;
+                    last_frame = Some(*record);
+                }
+                InputRecord::MousePos(x, y) => {
+                    w.write_all(&[TAG_MOUSE_POS])?;
+                    write_f32(w, x)?;
+                    write_f32(w, y)?;
+                }
+                InputRecord::MouseDown(states) => {
+                    let bits = states
+                        .iter()
+                        .enumerate()
+                        .fold(0, |bits, (i, &down)| bits | ((down as u8) << i));
+                    w.write_all(&[TAG_MOUSE_DOWN, bits])?;
+                }
+                InputRecord::MouseWheel(value) => {
+                    w.write_all(&[TAG_MOUSE_WHEEL])?;
+                    write_f32(w, value)?;
+                }
+                InputRecord::KeyCtrl(value) => w.write_all(&[TAG_KEY_CTRL, value as u8])?,
+                InputRecord::KeyShift(value) => w.write_all(&[TAG_KEY_SHIFT, value as u8])?,
+                InputRecord::KeyAlt(value) => w.write_all(&[TAG_KEY_ALT, value as u8])?,
+                InputRecord::KeySuper(value) => w.write_all(&[TAG_KEY_SUPER, value as u8])?,
+                InputRecord::Key(key, pressed) => {
+                    w.write_all(&[TAG_KEY, key.index() as u8, pressed as u8])?;
+                }
+                InputRecord::Char(c) => {
+                    w.write_all(&[TAG_CHAR])?;
+                    write_u32(w, c as u32)?;
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Reads a recording in the binary recording format.
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<InputRecording> {
+        let mut header = [0; 9];
+        r.read_exact(&mut header)?;
+        if &header[..8] != MAGIC {
+            return Err(invalid_data("Not an input recording"));
+        }
+        if header[8] != VERSION {
+            return Err(invalid_data("Unsupported input recording version"));
+        }
+        let mut recording = InputRecording::new();
+        let mut last_frame = None;
+        loop {
+            let mut tag = [0];
+            if r.read(&mut tag)? == 0 {
+                break;
+            }
+            let record = match tag[0] {
+                TAG_FRAME => {
+                    let size_points = (read_u32(r)?, read_u32(r)?);
+                    let size_pixels = (read_u32(r)?, read_u32(r)?);
+                    let frame = InputRecord::Frame {
+                        size_points: size_points,
+                        size_pixels: size_pixels,
+                        delta_time: read_f32(r)?,
+                    };
+                    last_frame = Some(frame);
+                    frame
+                }
+                TAG_REPEAT_FRAME => {
+                    last_frame.ok_or_else(|| invalid_data("Repeated frame without a frame"))?
+                }
+                TAG_MOUSE_POS => InputRecord::MousePos(read_f32(r)?, read_f32(r)?),
+                TAG_MOUSE_DOWN => {
+                    let bits = read_u8(r)?;
+                    let mut states = [false; 5];
+                    for (i, state) in states.iter_mut().enumerate() {
+                        *state = bits & (1 << i) != 0;
+                    }
+                    InputRecord::MouseDown(states)
+                }
+                TAG_MOUSE_WHEEL => InputRecord::MouseWheel(read_f32(r)?),
+                TAG_KEY_CTRL => InputRecord::KeyCtrl(read_bool(r)?),
+                TAG_KEY_SHIFT => InputRecord::KeyShift(read_bool(r)?),
+                TAG_KEY_ALT => InputRecord::KeyAlt(read_bool(r)?),
+                TAG_KEY_SUPER => InputRecord::KeySuper(read_bool(r)?),
+                TAG_KEY => {
+                    let key = Key::from_index(read_u8(r)? as usize)
+                        .ok_or_else(|| invalid_data("Invalid key"))?;
+                    InputRecord::Key(key, read_bool(r)?)
+                }
+                TAG_CHAR => {
+                    let c = ::std::char::from_u32(read_u32(r)?)
+                        .ok_or_else(|| invalid_data("Invalid character"))?;
+                    InputRecord::Char(c)
+                }
+                _ => return Err(invalid_data("Invalid input record")),
+            };
+            recording.push(record);
+        }
+        Ok(recording)
+    }
+    /// Saves the recording to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write_to(&mut w)?;
+        w.flush()
+    }
+    /// Loads a recording from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<InputRecording> {
+        InputRecording::read_from(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+/// Replays a recording frame by frame.
+///
+/// The recorded input is applied through the same `ImGui` setters it was recorded from, so
+/// replaying into a new context with the same fonts, style and UI code reproduces the same UI
+/// state. Input from other sources should not be applied while replaying.
+pub struct InputPlayer {
+    recording: InputRecording,
+    position: usize,
+    frames_played: usize,
+}
+
+impl InputPlayer {
+    pub fn new(recording: InputRecording) -> InputPlayer {
+        InputPlayer {
+            recording: recording,
+            position: 0,
+            frames_played: 0,
+        }
+    }
+    pub fn recording(&self) -> &InputRecording { &self.recording }
+    pub fn frames_played(&self) -> usize { self.frames_played }
+    /// Returns true if all recorded frames have been played.
+    pub fn is_finished(&self) -> bool { self.frames_played >= self.recording.frame_count() }
+    /// Starts playing from the beginning again.
+    pub fn rewind(&mut self) {
+        self.position = 0;
+        self.frames_played = 0;
+    }
+    /// Applies the input recorded before the next frame and starts that frame with the recorded
+    /// sizes and delta time. Returns `None` when there are no frames left.
+    pub fn next_frame<'ui, 'a: 'ui>(&mut self, imgui: &'a mut ImGui) -> Option<Ui<'ui>> {
+        while let Some(&record) = self.recording.records.get(self.position) {
+            self.position += 1;
+            match record {
+                InputRecord::Frame {
+                    size_points,
+                    size_pixels,
+                    delta_time,
+                } => {
+                    self.frames_played += 1;
+                    return Some(imgui.frame(size_points, size_pixels, delta_time));
+                }
+                InputRecord::MousePos(x, y) => imgui.set_mouse_pos(x, y),
+                InputRecord::MouseDown(states) => imgui.set_mouse_down(&states),
+                InputRecord::MouseWheel(value) => imgui.set_mouse_wheel(value),
+                InputRecord::KeyCtrl(value) => imgui.set_key_ctrl(value),
+                InputRecord::KeyShift(value) => imgui.set_key_shift(value),
+                InputRecord::KeyAlt(value) => imgui.set_key_alt(value),
+                InputRecord::KeySuper(value) => imgui.set_key_super(value),
+                InputRecord::Key(key, pressed) => imgui.set_key(key, pressed),
+                InputRecord::Char(c) => imgui.add_input_character(c),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor};
+
+    use super::*;
+    use super::super::{ImGuiCond, Ui};
+    use testing::TestHarness;
+
+    const FRAME: InputRecord = InputRecord::Frame {
+        size_points: (640, 480),
+        size_pixels: (1280, 960),
+        delta_time: 0.5,
+    };
+
+    fn encode(records: &[InputRecord]) -> Vec<u8> {
+        let mut recording = InputRecording::new();
+        for &record in records {
+            recording.push(record);
+        }
+        let mut data = Vec::new();
+        recording.write_to(&mut data).unwrap();
+        data
+    }
+
+    fn decode(data: &[u8]) -> io::Result<InputRecording> {
+        InputRecording::read_from(&mut Cursor::new(data))
+    }
+
+    fn header() -> Vec<u8> {
+        let mut data = MAGIC.to_vec();
+        data.push(VERSION);
+        data
+    }
+
+    #[test]
+    fn round_trip() {
+        let records = [
+            InputRecord::MousePos(12.5, -3.0),
+            InputRecord::MouseDown([true, false, true, false, true]),
+            FRAME,
+            InputRecord::MouseWheel(-1.0),
+            InputRecord::KeyCtrl(true),
+            InputRecord::KeyShift(false),
+            InputRecord::KeyAlt(true),
+            InputRecord::KeySuper(false),
+            InputRecord::Key(Key::Enter, true),
+            InputRecord::Char('ä'),
+            FRAME,
+            FRAME,
+        ];
+        let recording = decode(&encode(&records)).unwrap();
+        assert_eq!(recording.records(), &records[..]);
+        assert_eq!(recording.frame_count(), 3);
+    }
+
+    #[test]
+    fn repeated_frames_take_one_byte() {
+        let other_frame = InputRecord::Frame {
+            size_points: (640, 480),
+            size_pixels: (640, 480),
+            delta_time: 0.5,
+        };
+        let records = [FRAME, FRAME, FRAME, other_frame, other_frame, FRAME];
+        let data = encode(&records);
+        // Three full frames of a tag and 20 payload bytes, and three repeats
+        assert_eq!(data.len(), header().len() + 3 * 21 + 3);
+        assert_eq!(data[header().len() + 21], TAG_REPEAT_FRAME);
+        assert_eq!(decode(&data).unwrap().records(), &records[..]);
+    }
+
+    #[test]
+    fn mouse_down_bits() {
+        let data = encode(&[InputRecord::MouseDown([true, true, false, false, true])]);
+        assert_eq!(&data[header().len()..], &[TAG_MOUSE_DOWN, 0b10011]);
+        for i in 0..5 {
+            let mut states = [false; 5];
+            states[i] = true;
+            let recording = decode(&encode(&[InputRecord::MouseDown(states)])).unwrap();
+            assert_eq!(recording.records(), &[InputRecord::MouseDown(states)]);
+        }
+    }
+
+    #[test]
+    fn invalid_data() {
+        let invalid = |payload: &[u8]| {
+            let mut data = header();
+            data.extend_from_slice(payload);
+            decode(&data).unwrap_err().kind()
+        };
+        assert_eq!(invalid(&[99]), io::ErrorKind::InvalidData);
+        assert_eq!(invalid(&[TAG_REPEAT_FRAME]), io::ErrorKind::InvalidData);
+        assert_eq!(invalid(&[TAG_KEY, 255, 1]), io::ErrorKind::InvalidData);
+        assert_eq!(invalid(&[TAG_KEY_CTRL, 2]), io::ErrorKind::InvalidData);
+        // A surrogate is not a char
+        assert_eq!(invalid(&[TAG_CHAR, 0x00, 0xd8, 0, 0]), io::ErrorKind::InvalidData);
+
+        let mut data = header();
+        data[0] = b'X';
+        assert_eq!(decode(&data).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let mut data = header();
+        data[MAGIC.len()] = VERSION + 1;
+        assert_eq!(decode(&data).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    fn button_ui(ui: &Ui, clicks: &mut u32) -> (f32, f32) {
+        let mut center = (0.0, 0.0);
+        ui.window("Recorded")
+            .position((10.0, 10.0), ImGuiCond::Always)
+            .build(|| {
+                if ui.button("Click me") {
+                    *clicks += 1;
+                }
+                let (min, max) = (ui.get_item_rect_min(), ui.get_item_rect_max());
+                center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+            });
+        center
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut harness = TestHarness::new();
+        let mut clicks = 0;
+        harness.imgui_mut().start_recording();
+        let (x, y) = harness.frame(|ui| button_ui(ui, &mut clicks));
+        harness.click_at(x, y);
+        harness.run_until_idle(&mut |ui| { button_ui(ui, &mut clicks); });
+        harness.click_at(x, y);
+        harness.run_until_idle(&mut |ui| { button_ui(ui, &mut clicks); });
+        let recording = harness.imgui_mut().stop_recording().unwrap();
+        assert_eq!(clicks, 2);
+
+        let mut data = Vec::new();
+        recording.write_to(&mut data).unwrap();
+        let loaded = InputRecording::read_from(&mut Cursor::new(data)).unwrap();
+        let mut player = InputPlayer::new(loaded);
+        let mut replayed = TestHarness::new();
+        let mut replayed_clicks = 0;
+        while let Some(ui) = player.next_frame(replayed.imgui_mut()) {
+            button_ui(&ui, &mut replayed_clicks);
+            ui.render();
+        }
+        assert!(player.is_finished());
+        assert_eq!(player.frames_played(), recording.frame_count());
+        assert_eq!(replayed_clicks, 2);
+    }
+}
@@ -0,0 +1,384 @@
+use sys;
+use std::os::raw::c_void;
+use std::slice;
+
+use super::{ImDrawIdx, ImDrawVert, ImGui, ImVec2, ImVec4, TextureId};
+
+/// A draw callback registered with `WindowDrawList::add_callback`. It receives the clip
+/// rectangle of its draw command as `[x1, y1, x2, y2]` in display coordinates.
+pub(crate) type DrawCallbackFn = Box<FnMut([f32; 4])>;
+
+/// User callback data marking a request to reset the render state.
+const RESET_RENDER_STATE: usize = !0;
+
+/// Callback function installed in draw commands created by imgui-rs. It is never called by
+/// renderers, which invoke `DrawCallback` instead, but imgui needs a non-null function pointer
+/// to recognize the command as a callback.
+pub(crate) extern "C" fn draw_callback_marker(
+    _: *const sys::ImDrawList,
+    _: *const sys::ImDrawCmd,
+) {
+}
+
+pub(crate) fn reset_render_state_data() -> *mut c_void { RESET_RENDER_STATE as *mut c_void }
+
+/// Creates a draw command that requests a render state reset.
+pub(crate) fn reset_render_state_cmd() -> sys::ImDrawCmd {
+    sys::ImDrawCmd {
+        elem_count: 0,
+        clip_rect: ImVec4::new(0.0, 0.0, 0.0, 0.0),
+        texture_id: TextureId::new(0).as_raw(),
+        user_callback: Some(draw_callback_marker),
+        user_callback_data: reset_render_state_data(),
+    }
+}
+
+/// All draw lists of a rendered frame, returned by `Ui::render` or `OwnedDrawData::draw_data`.
+pub struct DrawData<'a> {
+    source: DrawDataSource<'a>,
+}
+
+enum DrawDataSource<'a> {
+    Imgui {
+        raw: *mut sys::ImDrawData,
+        imgui: &'a ImGui,
+    },
+    Owned(&'a mut OwnedDrawData),
+}
+
+impl<'a> DrawData<'a> {
+    pub(crate) unsafe fn new(imgui: &'a ImGui, raw: *mut sys::ImDrawData) -> DrawData<'a> {
+        DrawData {
+            source: DrawDataSource::Imgui {
+                raw: raw,
+                imgui: imgui,
+            },
+        }
+    }
+
+    /// Returns the context that rendered the draw data, or `None` if it's owned draw data.
+    pub(crate) fn imgui(&self) -> Option<&'a ImGui> {
+        match self.source {
+            DrawDataSource::Imgui { imgui, .. } => Some(imgui),
+            DrawDataSource::Owned(_) => None,
+        }
+    }
+
+    /// Returns false if there is nothing to render.
+    pub fn is_valid(&self) -> bool {
+        match self.source {
+            DrawDataSource::Imgui { raw, .. } => unsafe { (*raw).valid },
+            DrawDataSource::Owned(_) => true,
+        }
+    }
+    /// Returns the display size the draw data was rendered for.
+    pub fn display_size(&self) -> (f32, f32) {
+        match self.source {
+            DrawDataSource::Imgui { imgui, .. } => imgui.display_size(),
+            DrawDataSource::Owned(ref owned) => owned.display_size,
+        }
+    }
+    /// Returns the number of draw lists.
+    pub fn draw_list_count(&self) -> usize {
+        match self.source {
+            DrawDataSource::Imgui { raw, .. } => unsafe { (*raw).cmd_lists_count as usize },
+            DrawDataSource::Owned(ref owned) => owned.draw_lists.len(),
+        }
+    }
+    /// Returns the total number of vertices in all draw lists.
+    pub fn total_vtx_count(&self) -> usize {
+        match self.source {
+            DrawDataSource::Imgui { raw, .. } => unsafe { (*raw).total_vtx_count as usize },
+            DrawDataSource::Owned(ref owned) => {
+                owned.draw_lists.iter().map(|list| list.vtx_buffer.len()).sum()
+            }
+        }
+    }
+    /// Returns the total number of indices in all draw lists.
+    pub fn total_idx_count(&self) -> usize {
+        match self.source {
+            DrawDataSource::Imgui { raw, .. } => unsafe { (*raw).total_idx_count as usize },
+            DrawDataSource::Owned(ref owned) => {
+                owned.draw_lists.iter().map(|list| list.idx_buffer.len()).sum()
+            }
+        }
+    }
+    /// Returns an iterator over the draw lists.
+    pub fn draw_lists<'b>(&'b self) -> DrawListIterator<'b> {
+        let iter = match self.source {
+            DrawDataSource::Imgui { raw, imgui } => {
+                let cmd_lists = if self.is_valid() {
+                    unsafe { (*raw).cmd_lists() }
+                } else {
+                    &[]
+                };
+                DrawListSource::Imgui(cmd_lists.iter(), imgui)
+            }
+            DrawDataSource::Owned(ref owned) => DrawListSource::Owned(owned.draw_lists.iter()),
+        };
+        DrawListIterator { iter: iter }
+    }
+    /// Converts all draw lists to non-indexed triangle lists, for backends that don't support
+    /// indexed drawing. The index buffers are empty afterwards, and every `Elements` command
+    /// covers `count` vertices instead.
+    ///
+    /// This is slow and uses more memory, so only use it if indexed drawing isn't available.
+    pub fn deindex_all_buffers(&mut self) {
+        match self.source {
+            DrawDataSource::Imgui { raw, imgui } => {
+                imgui.with_context(|| unsafe { sys::ImDrawData_DeIndexAllBuffers(raw) })
+            }
+            DrawDataSource::Owned(ref mut owned) => {
+                for list in &mut owned.draw_lists {
+                    if list.idx_buffer.is_empty() {
+                        continue;
+                    }
+                    let vtx_buffer = list.idx_buffer
+                        .iter()
+                        .map(|&idx| list.vtx_buffer[idx as usize])
+                        .collect();
+                    list.vtx_buffer = vtx_buffer;
+                    list.idx_buffer.clear();
+                }
+            }
+        }
+    }
+    /// Scales all clip rectangles, e.g. by `display_framebuffer_scale` if the renderer works
+    /// in pixels instead of points.
+    pub fn scale_clip_rects<S: Into<ImVec2>>(&mut self, scale: S) {
+        let scale = scale.into();
+        match self.source {
+            DrawDataSource::Imgui { raw, imgui } => {
+                imgui.with_context(|| unsafe { sys::ImDrawData_ScaleClipRects(raw, scale) })
+            }
+            DrawDataSource::Owned(ref mut owned) => {
+                for cmd in owned.draw_lists.iter_mut().flat_map(|list| &mut list.cmd_buffer) {
+                    let r = cmd.clip_rect;
+                    cmd.clip_rect =
+                        ImVec4::new(r.x * scale.x, r.y * scale.y, r.z * scale.x, r.w * scale.y);
+                }
+            }
+        }
+    }
+}
+
+/// Draw lists owned by Rust instead of imgui, e.g. copied from a rendered frame or loaded from
+/// a `FrameCapture`. They can be passed to renderers through `draw_data`.
+///
+/// Draw callbacks can't outlive their frame, so only `DrawCmd::ResetRenderState` commands are
+/// kept when draw data is copied.
+pub struct OwnedDrawData {
+    pub display_size: (f32, f32),
+    pub draw_lists: Vec<OwnedDrawList>,
+}
+
+/// A draw list of `OwnedDrawData`.
+pub struct OwnedDrawList {
+    pub cmd_buffer: Vec<sys::ImDrawCmd>,
+    pub idx_buffer: Vec<ImDrawIdx>,
+    pub vtx_buffer: Vec<ImDrawVert>,
+}
+
+impl OwnedDrawData {
+    pub fn new(display_size: (f32, f32)) -> OwnedDrawData {
+        OwnedDrawData {
+            display_size: display_size,
+            draw_lists: Vec::new(),
+        }
+    }
+    /// Returns the draw data in the form renderers accept.
+    pub fn draw_data<'a>(&'a mut self) -> DrawData<'a> {
+        DrawData { source: DrawDataSource::Owned(self) }
+    }
+}
+
+impl<'a, 'b> From<&'b DrawData<'a>> for OwnedDrawData {
+    fn from(draw_data: &'b DrawData<'a>) -> OwnedDrawData {
+        let mut owned = OwnedDrawData::new(draw_data.display_size());
+        for draw_list in draw_data {
+            let mut cmd_buffer = Vec::with_capacity(draw_list.cmd_buffer.len());
+            for cmd in draw_list.commands() {
+                match cmd {
+                    DrawCmd::Elements {
+                        count,
+                        clip_rect,
+                        texture_id,
+                    } => {
+                        cmd_buffer.push(sys::ImDrawCmd {
+                            elem_count: count as u32,
+                            clip_rect: clip_rect.into(),
+                            texture_id: texture_id.as_raw(),
+                            user_callback: None,
+                            user_callback_data: ::std::ptr::null_mut(),
+                        })
+                    }
+                    DrawCmd::ResetRenderState => cmd_buffer.push(reset_render_state_cmd()),
+                    DrawCmd::Callback(_) => (),
+                }
+            }
+            owned.draw_lists.push(OwnedDrawList {
+                cmd_buffer: cmd_buffer,
+                idx_buffer: draw_list.idx_buffer.to_vec(),
+                vtx_buffer: draw_list.vtx_buffer.to_vec(),
+            });
+        }
+        owned
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b DrawData<'a> {
+    type Item = DrawList<'b>;
+    type IntoIter = DrawListIterator<'b>;
+
+    fn into_iter(self) -> DrawListIterator<'b> { self.draw_lists() }
+}
+
+/// Iterator over the draw lists of a `DrawData`.
+pub struct DrawListIterator<'a> {
+    iter: DrawListSource<'a>,
+}
+
+enum DrawListSource<'a> {
+    Imgui(slice::Iter<'a, *const sys::ImDrawList>, &'a ImGui),
+    Owned(slice::Iter<'a, OwnedDrawList>),
+}
+
+impl<'a> Iterator for DrawListIterator<'a> {
+    type Item = DrawList<'a>;
+
+    fn next(&mut self) -> Option<DrawList<'a>> {
+        match self.iter {
+            DrawListSource::Imgui(ref mut iter, imgui) => {
+                iter.next().map(|&raw| unsafe { DrawList::new(imgui, raw) })
+            }
+            DrawListSource::Owned(ref mut iter) => iter.next().map(|list| DrawList {
+                cmd_buffer: &list.cmd_buffer,
+                idx_buffer: &list.idx_buffer,
+                vtx_buffer: &list.vtx_buffer,
+                raw: ::std::ptr::null(),
+                imgui: None,
+            }),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.iter {
+            DrawListSource::Imgui(ref iter, _) => iter.size_hint(),
+            DrawListSource::Owned(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+/// A single draw list, generally one per window.
+pub struct DrawList<'a> {
+    pub cmd_buffer: &'a [sys::ImDrawCmd],
+    pub idx_buffer: &'a [sys::ImDrawIdx],
+    pub vtx_buffer: &'a [sys::ImDrawVert],
+    raw: *const sys::ImDrawList,
+    // None for owned draw lists, which have no callbacks
+    imgui: Option<&'a ImGui>,
+}
+
+impl<'a> DrawList<'a> {
+    pub(crate) unsafe fn new(imgui: &'a ImGui, raw: *const sys::ImDrawList) -> DrawList<'a> {
+        DrawList {
+            cmd_buffer: (*raw).cmd_buffer.as_slice(),
+            idx_buffer: (*raw).idx_buffer.as_slice(),
+            vtx_buffer: (*raw).vtx_buffer.as_slice(),
+            raw: raw,
+            imgui: Some(imgui),
+        }
+    }
+
+    /// Returns an iterator over the draw commands of this list.
+    pub fn commands(&self) -> DrawCmdIterator<'a> {
+        DrawCmdIterator {
+            iter: self.cmd_buffer.iter(),
+            raw: self.raw,
+            imgui: self.imgui,
+        }
+    }
+}
+
+/// A single draw command of a `DrawList`.
+pub enum DrawCmd<'a> {
+    /// Draws `count` indices from the index buffer as triangles, starting after the indices
+    /// of all previous `Elements` commands in the same list.
+    Elements {
+        count: usize,
+        /// Clip rectangle as `[x1, y1, x2, y2]` in display coordinates
+        clip_rect: [f32; 4],
+        texture_id: TextureId,
+    },
+    /// The renderer should restore its own render state, e.g. because a previous callback has
+    /// changed it.
+    ResetRenderState,
+    /// A user callback, which the renderer should invoke in order with the other commands.
+    Callback(DrawCallback<'a>),
+}
+
+/// A user callback in a draw list, see `DrawCmd::Callback`.
+pub struct DrawCallback<'a> {
+    cmd: &'a sys::ImDrawCmd,
+    raw: *const sys::ImDrawList,
+    imgui: Option<&'a ImGui>,
+}
+
+impl<'a> DrawCallback<'a> {
+    /// Returns the clip rectangle as `[x1, y1, x2, y2]` in display coordinates.
+    pub fn clip_rect(&self) -> [f32; 4] { self.cmd.clip_rect.into() }
+    /// Runs the callback.
+    ///
+    /// Callbacks registered directly through imgui-sys are called with the raw draw list and
+    /// draw command.
+    pub fn invoke(&self) {
+        if is_rust_callback(self.cmd) {
+            let index = self.cmd.user_callback_data as usize;
+            if let Some(imgui) = self.imgui {
+                let mut callbacks = imgui.draw_callbacks.borrow_mut();
+                if let Some(callback) = callbacks.get_mut(index) {
+                    callback(self.clip_rect());
+                }
+            }
+        } else if let Some(callback) = self.cmd.user_callback {
+            callback(self.raw, self.cmd);
+        }
+    }
+}
+
+fn is_rust_callback(cmd: &sys::ImDrawCmd) -> bool {
+    let marker: extern "C" fn(*const sys::ImDrawList, *const sys::ImDrawCmd) =
+        draw_callback_marker;
+    cmd.user_callback.map(|f| f as usize) == Some(marker as usize)
+}
+
+/// Iterator over the draw commands of a `DrawList`.
+pub struct DrawCmdIterator<'a> {
+    iter: slice::Iter<'a, sys::ImDrawCmd>,
+    raw: *const sys::ImDrawList,
+    imgui: Option<&'a ImGui>,
+}
+
+impl<'a> Iterator for DrawCmdIterator<'a> {
+    type Item = DrawCmd<'a>;
+
+    fn next(&mut self) -> Option<DrawCmd<'a>> {
+        self.iter.next().map(|cmd| if cmd.user_callback.is_none() {
+            DrawCmd::Elements {
+                count: cmd.elem_count as usize,
+                clip_rect: cmd.clip_rect.into(),
+                texture_id: TextureId::from(cmd.texture_id),
+            }
+        } else if is_rust_callback(cmd) && cmd.user_callback_data as usize == RESET_RENDER_STATE {
+            DrawCmd::ResetRenderState
+        } else {
+            DrawCmd::Callback(DrawCallback {
+                cmd: cmd,
+                raw: self.raw,
+                imgui: self.imgui,
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The saved state of a window, as stored by `ImGui::save_settings_to_string`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowSettings {
+    /// The window name. For names containing `###`, only the part starting at `###` is used,
+    /// because it identifies the window regardless of the displayed title
+    pub name: String,
+    pub pos: (f32, f32),
+    /// The size of the window when it's not collapsed. A zero size leaves the size unchanged
+    pub size: (f32, f32),
+    pub collapsed: bool,
+}
+
+impl WindowSettings {
+    pub fn new(name: &str) -> WindowSettings {
+        WindowSettings {
+            name: settings_name(name).to_owned(),
+            pos: (0.0, 0.0),
+            size: (0.0, 0.0),
+            collapsed: false,
+        }
+    }
+
+    /// Parses window settings in the `imgui.ini` format.
+    ///
+    /// Like dear imgui, this ignores other sections, unknown keys and values that can't be
+    /// parsed.
+    pub fn parse(data: &str) -> Vec<WindowSettings> {
+        let mut windows: Vec<WindowSettings> = Vec::new();
+        let mut in_window = false;
+        for line in data.lines() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                in_window = line.starts_with("[Window][") && line.ends_with(']');
+                if in_window {
+                    let name = &line["[Window][".len()..line.len() - 1];
+                    windows.push(WindowSettings::new(name));
+                }
+                continue;
+            }
+            if !in_window {
+                continue;
+            }
+            let window = match windows.last_mut() {
+                Some(window) => window,
+                None => continue,
+            };
+            let mut parts = line.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => continue,
+            };
+            match key {
+                "Pos" => {
+                    if let Some(pos) = parse_pair(value) {
+                        window.pos = pos;
+                    }
+                }
+                "Size" => {
+                    if let Some(size) = parse_pair(value) {
+                        window.size = size;
+                    }
+                }
+                "Collapsed" => {
+                    if let Ok(collapsed) = value.parse::<i32>() {
+                        window.collapsed = collapsed != 0;
+                    }
+                }
+                _ => (),
+            }
+        }
+        windows
+    }
+
+    /// Formats window settings in the `imgui.ini` format, which dear imgui can also load.
+    pub fn serialize(windows: &[WindowSettings]) -> String {
+        let mut data = String::new();
+        for window in windows {
+            // Writing to a String can't fail
+            let _ = write!(
+                data,
+                "[Window][{}]\nPos={},{}\nSize={},{}\nCollapsed={}\n\n",
+                window.name,
+                window.pos.0 as i32,
+                window.pos.1 as i32,
+                window.size.0 as i32,
+                window.size.1 as i32,
+                window.collapsed as i32
+            );
+        }
+        data
+    }
+}
+
+/// Returns the part of a window name that identifies the window in the settings.
+pub(crate) fn settings_name(name: &str) -> &str {
+    match name.find("###") {
+        Some(start) => &name[start..],
+        None => name,
+    }
+}
+
+fn parse_pair(value: &str) -> Option<(f32, f32)> {
+    let mut parts = value.splitn(2, ',');
+    match (parts.next(), parts.next()) {
+        (Some(x), Some(y)) => match (x.trim().parse(), y.trim().parse()) {
+            (Ok(x), Ok(y)) => Some((x, y)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The window settings of a context, kept up to date by `Window::build`.
+#[derive(Default)]
+pub(crate) struct SettingsStore {
+    // In the order the windows first appeared
+    windows: Vec<WindowSettings>,
+    // Window names to indices in `windows`, since every window is recorded every frame
+    indices: HashMap<String, usize>,
+    // Loaded settings that are applied when their window is built the next time
+    pending: HashMap<String, WindowSettings>,
+}
+
+impl SettingsStore {
+    pub fn windows(&self) -> &[WindowSettings] { &self.windows }
+    pub fn apply(&mut self, settings: &WindowSettings) {
+        let mut settings = settings.clone();
+        let name = settings_name(&settings.name).to_owned();
+        settings.name = name;
+        self.update(&settings);
+        self.pending.insert(settings.name.clone(), settings);
+    }
+    pub fn take_pending(&mut self, name: &str) -> Option<WindowSettings> {
+        self.pending.remove(name)
+    }
+    /// Records the current state of a window. The size of a collapsed window is not its full
+    /// size, so the previous size is kept for collapsed windows.
+    pub fn record(&mut self, name: &str, pos: (f32, f32), size: (f32, f32), collapsed: bool) {
+        let index = match self.indices.get(name) {
+            Some(&index) => index,
+            None => {
+                let mut window = WindowSettings::new(name);
+                window.size = size;
+                self.push(window)
+            }
+        };
+        let window = &mut self.windows[index];
+        window.pos = pos;
+        if !collapsed {
+            window.size = size;
+        }
+        window.collapsed = collapsed;
+    }
+    fn update(&mut self, settings: &WindowSettings) {
+        match self.indices.get(&settings.name) {
+            Some(&index) => {
+                let window = &mut self.windows[index];
+                window.pos = settings.pos;
+                if settings.size != (0.0, 0.0) {
+                    window.size = settings.size;
+                }
+                window.collapsed = settings.collapsed;
+            }
+            None => {
+                self.push(settings.clone());
+            }
+        }
+    }
+    fn push(&mut self, settings: WindowSettings) -> usize {
+        let index = self.windows.len();
+        self.indices.insert(settings.name.clone(), index);
+        self.windows.push(settings);
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(name: &str, pos: (f32, f32), size: (f32, f32), collapsed: bool) -> WindowSettings {
+        WindowSettings {
+            name: name.to_owned(),
+            pos: pos,
+            size: size,
+            collapsed: collapsed,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let windows = vec![
+            window("Debug", (60.0, 60.0), (400.0, 400.0), false),
+            window("Tools", (-10.0, 20.0), (300.0, 200.0), true),
+        ];
+        let data = WindowSettings::serialize(&windows);
+        assert_eq!(
+            data,
+            "[Window][Debug]\nPos=60,60\nSize=400,400\nCollapsed=0\n\n\
+             [Window][Tools]\nPos=-10,20\nSize=300,200\nCollapsed=1\n\n"
+        );
+        assert_eq!(WindowSettings::parse(&data), windows);
+    }
+
+    #[test]
+    fn triple_hash_names() {
+        assert_eq!(WindowSettings::new("Title###id").name, "###id");
+        assert_eq!(WindowSettings::new("Title##id").name, "Title##id");
+        let windows = WindowSettings::parse("[Window][Frame 12###Stats]\nPos=1,2\n");
+        assert_eq!(windows, vec![window("###Stats", (1.0, 2.0), (0.0, 0.0), false)]);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let data = "Pos=5,5\n\
+                    [Window][A]\n\
+                    Pos=10\n\
+                    Size=abc,20\n\
+                    Collapsed=yes\n\
+                    Unknown=1\n\
+                    no equals sign\n\
+                    [Window][B\n\
+                    Pos=7,7\n\
+                    [Docking][Data]\n\
+                    Pos=8,8\n\
+                    [Window][C]\n\
+                    Pos = 3 , 4\n";
+        assert_eq!(
+            WindowSettings::parse(data),
+            vec![
+                window("A", (0.0, 0.0), (0.0, 0.0), false),
+                window("C", (3.0, 4.0), (0.0, 0.0), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapsed_windows_keep_their_size() {
+        let mut store = SettingsStore::default();
+        store.record("A", (10.0, 10.0), (300.0, 200.0), false);
+        // A collapsed window is only as tall as its title bar
+        store.record("A", (20.0, 20.0), (300.0, 19.0), true);
+        assert_eq!(store.windows(), &[window("A", (20.0, 20.0), (300.0, 200.0), true)][..]);
+
+        // A window that first appears collapsed has no better size to keep
+        store.record("B", (0.0, 0.0), (100.0, 19.0), true);
+        assert_eq!(store.windows()[1], window("B", (0.0, 0.0), (100.0, 19.0), true));
+    }
+
+    #[test]
+    fn applied_settings() {
+        let mut store = SettingsStore::default();
+        store.record("###A", (10.0, 10.0), (300.0, 200.0), false);
+        store.apply(&window("Title###A", (50.0, 60.0), (0.0, 0.0), true));
+        store.apply(&window("B", (1.0, 2.0), (3.0, 4.0), false));
+        // A zero size leaves the recorded size unchanged
+        assert_eq!(
+            store.windows(),
+            &[
+                window("###A", (50.0, 60.0), (300.0, 200.0), true),
+                window("B", (1.0, 2.0), (3.0, 4.0), false),
+            ][..]
+        );
+        assert_eq!(
+            store.take_pending("###A"),
+            Some(window("###A", (50.0, 60.0), (0.0, 0.0), true))
+        );
+        assert_eq!(store.take_pending("###A"), None);
+    }
+}
@@ -1,7 +1,6 @@
 use sys;
-use std::marker::PhantomData;
 
-use super::Ui;
+use super::{ImGui, Ui};
 
 // TODO: Consider using Range, even though it is half-open
 
@@ -12,18 +11,18 @@ pub struct SliderInt<'ui, 'p> {
     min: i32,
     max: i32,
     display_format: &'p str,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> SliderInt<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut i32, min: i32, max: i32) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut i32, min: i32, max: i32) -> Self {
         SliderInt {
             label: label,
             value: value,
             min: min,
             max: max,
             display_format: "%.0f",
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -32,6 +31,7 @@ impl<'ui, 'p> SliderInt<'ui, 'p> {
         self
     }
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         unsafe {
             sys::igSliderInt(
                 sys::ImStr::from(self.label),
@@ -53,18 +53,18 @@ macro_rules! impl_slider_intn {
             min: i32,
             max: i32,
             display_format: &'p str,
-            _phantom: PhantomData<&'ui Ui<'ui>>,
+            imgui: &'ui ImGui,
         }
 
         impl<'ui, 'p> $SliderIntN<'ui, 'p> {
-            pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut [i32; $N], min: i32, max: i32) -> Self {
+            pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut [i32; $N], min: i32, max: i32) -> Self {
                 $SliderIntN {
                     label: label,
                     value: value,
                     min: min,
                     max: max,
                     display_format: "%.0f",
-                    _phantom: PhantomData,
+                    imgui: ui.imgui,
                 }
             }
             #[inline]
@@ -73,6 +73,7 @@ macro_rules! impl_slider_intn {
                 self
             }
             pub fn build(self) -> bool {
+                self.imgui.make_current();
                 unsafe {
                     sys::$igSliderIntN(
                         sys::ImStr::from(self.label),
@@ -98,11 +99,11 @@ pub struct SliderFloat<'ui, 'p> {
     max: f32,
     display_format: &'p str,
     power: f32,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> SliderFloat<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut f32, min: f32, max: f32) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut f32, min: f32, max: f32) -> Self {
         SliderFloat {
             label: label,
             value: value,
@@ -110,7 +111,7 @@ impl<'ui, 'p> SliderFloat<'ui, 'p> {
             max: max,
             display_format: "%.3f",
             power: 1.0,
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -124,6 +125,7 @@ impl<'ui, 'p> SliderFloat<'ui, 'p> {
         self
     }
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         unsafe {
             sys::igSliderFloat(
                 sys::ImStr::from(self.label),
@@ -147,11 +149,11 @@ macro_rules! impl_slider_floatn {
             max: f32,
             display_format: &'p str,
             power: f32,
-            _phantom: PhantomData<&'ui Ui<'ui>>,
+            imgui: &'ui ImGui,
         }
 
         impl<'ui, 'p> $SliderFloatN<'ui, 'p> {
-            pub fn new(_: &Ui<'ui>, label: &'p str, value: &'p mut [f32; $N], min: f32, max: f32) -> Self {
+            pub fn new(ui: &Ui<'ui>, label: &'p str, value: &'p mut [f32; $N], min: f32, max: f32) -> Self {
                 $SliderFloatN {
                     label: label,
                     value: value,
@@ -159,7 +161,7 @@ macro_rules! impl_slider_floatn {
                     max: max,
                     display_format: "%.3f",
                     power: 1.0,
-                    _phantom: PhantomData,
+                    imgui: ui.imgui,
                 }
             }
             #[inline]
@@ -173,6 +175,7 @@ macro_rules! impl_slider_floatn {
                 self
             }
             pub fn build(self) -> bool {
+                self.imgui.make_current();
                 unsafe {
                     sys::$igSliderFloatN(
                         sys::ImStr::from(self.label),
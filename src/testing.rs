@@ -0,0 +1,247 @@
+//! Support for testing user interfaces without a window or a GPU.
+//!
+//! `TestHarness` runs frames with a fixed display size and delta time, and scripts input
+//! through the input event queue of `ImGui`. Results can be queried inside a frame with the
+//! item and window queries of `Ui`, such as `Ui::get_item_rect_min` and `Ui::is_item_active`.
+//!
+//! # Example
+//! ```rust
+//! # use imgui::*;
+//! use imgui::testing::TestHarness;
+//!
+//! fn test_ui(ui: &Ui, clicks: &mut u32) -> (f32, f32) {
+//!     let mut button_center = (0.0, 0.0);
+//!     ui.window("Test")
+//!         .position((10.0, 10.0), ImGuiCond::Always)
+//!         .build(|| {
+//!             if ui.button("Click me") {
+//!                 *clicks += 1;
+//!             }
+//!             let (min, max) = (ui.get_item_rect_min(), ui.get_item_rect_max());
+//!             button_center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+//!         });
+//!     button_center
+//! }
+//!
+//! let mut harness = TestHarness::new();
+//! let mut clicks = 0;
+//! let (x, y) = harness.frame(|ui| test_ui(ui, &mut clicks));
+//! harness.click_at(x, y);
+//! harness.run_until_idle(&mut |ui| { test_ui(ui, &mut clicks); });
+//! assert_eq!(clicks, 1);
+//! ```
+use super::{ImGui, InputEvent, Key, MouseButton, Ui};
+
+/// Runs an `ImGui` context headlessly with scripted input.
+pub struct TestHarness {
+    imgui: ImGui,
+    display_size: (u32, u32),
+    delta_time: f32,
+}
+
+impl TestHarness {
+    /// Creates a harness with a new context, a 1280x720 display and a delta time of 1/60 s.
+    pub fn new() -> TestHarness { TestHarness::with_imgui(ImGui::init()) }
+    /// Creates a harness that runs the given context, e.g. one with custom fonts or style.
+    ///
+    /// The font atlas is built immediately, so fonts need to be added before this. Settings
+    /// are not loaded from or saved to `imgui.ini`.
+    pub fn with_imgui(mut imgui: ImGui) -> TestHarness {
+        imgui.set_ini_filename(None);
+        // imgui requires a built font atlas, but the pixels aren't needed without a renderer
+        imgui.prepare_texture(|_| ());
+        TestHarness {
+            imgui: imgui,
+            display_size: (1280, 720),
+            delta_time: 1.0 / 60.0,
+        }
+    }
+    pub fn imgui(&self) -> &ImGui { &self.imgui }
+    pub fn imgui_mut(&mut self) -> &mut ImGui { &mut self.imgui }
+    pub fn display_size(&self) -> (u32, u32) { self.display_size }
+    pub fn set_display_size(&mut self, width: u32, height: u32) {
+        self.display_size = (width, height);
+    }
+    pub fn delta_time(&self) -> f32 { self.delta_time }
+    pub fn set_delta_time(&mut self, delta_time: f32) { self.delta_time = delta_time; }
+
+    /// Runs one frame and returns the result of the ui function.
+    ///
+    /// Queued input is applied at the start of the frame. The frame is rendered, but the draw
+    /// data is discarded.
+    pub fn frame<F: FnOnce(&Ui) -> R, R>(&mut self, f: F) -> R {
+        let ui = self.imgui.frame(self.display_size, self.display_size, self.delta_time);
+        let result = f(&ui);
+        ui.render();
+        result
+    }
+    /// Runs one frame and passes the built `Ui` to a render function, e.g. the `render` method
+    /// of a renderer, instead of discarding the draw data.
+    pub fn render_frame<F, R, T>(&mut self, f: F, render: R) -> T
+    where
+        F: FnOnce(&Ui),
+        R: for<'ui> FnOnce(Ui<'ui>) -> T,
+    {
+        let ui = self.imgui.frame(self.display_size, self.display_size, self.delta_time);
+        f(&ui);
+        render(ui)
+    }
+    /// Runs one frame with a ui function that is used for several frames.
+    pub fn run_frame<F: FnMut(&Ui)>(&mut self, f: &mut F) { self.frame(|ui| f(ui)) }
+    /// Runs the given number of frames.
+    pub fn run_frames<F: FnMut(&Ui)>(&mut self, count: usize, f: &mut F) {
+        for _ in 0..count {
+            self.run_frame(f);
+        }
+    }
+    /// Runs frames until all queued input has been applied, followed by one more frame so that
+    /// the effects of the last input are visible. Returns the number of frames run.
+    pub fn run_until_idle<F: FnMut(&Ui)>(&mut self, f: &mut F) -> usize {
+        let mut count = 0;
+        while self.has_pending_input() {
+            self.run_frame(f);
+            count += 1;
+        }
+        self.run_frame(f);
+        count + 1
+    }
+    /// Returns true if some of the scripted input hasn't been applied yet.
+    pub fn has_pending_input(&self) -> bool { self.imgui.queued_event_count() > 0 }
+
+    /// Queues an arbitrary input event.
+    pub fn push_event(&mut self, event: InputEvent) { self.imgui.push_event(event); }
+    /// Moves the mouse to a position in display coordinates.
+    pub fn move_mouse_to(&mut self, x: f32, y: f32) {
+        self.push_event(InputEvent::MousePos(x, y));
+    }
+    pub fn mouse_down(&mut self, button: MouseButton) {
+        self.push_event(InputEvent::MouseButton(button, true));
+    }
+    pub fn mouse_up(&mut self, button: MouseButton) {
+        self.push_event(InputEvent::MouseButton(button, false));
+    }
+    /// Presses and releases a mouse button at the current mouse position. The press and the
+    /// release are applied in different frames.
+    pub fn click(&mut self, button: MouseButton) {
+        self.mouse_down(button);
+        self.mouse_up(button);
+    }
+    /// Moves the mouse to a position and clicks the left mouse button there.
+    pub fn click_at(&mut self, x: f32, y: f32) {
+        self.move_mouse_to(x, y);
+        self.click(MouseButton::Left);
+    }
+    /// Scrolls the mouse wheel by the given number of lines.
+    pub fn scroll(&mut self, lines: f32) { self.push_event(InputEvent::MouseWheel(lines)); }
+    pub fn key_down(&mut self, key: Key) { self.push_event(InputEvent::Key(key, true)); }
+    pub fn key_up(&mut self, key: Key) { self.push_event(InputEvent::Key(key, false)); }
+    /// Presses and releases a key.
+    pub fn press_key(&mut self, key: Key) {
+        self.key_down(key);
+        self.key_up(key);
+    }
+    /// Presses and releases a key while holding down a modifier key, e.g. `Key::LeftCtrl`.
+    pub fn press_key_with(&mut self, modifier: Key, key: Key) {
+        self.key_down(modifier);
+        self.press_key(key);
+        self.key_up(modifier);
+    }
+    /// Types text into the active text input.
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.push_event(InputEvent::Char(c));
+        }
+    }
+}
+
+impl Default for TestHarness {
+    fn default() -> TestHarness { TestHarness::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{ImGuiCond, ImString};
+
+    /// Runs frames until the input is idle and returns the mouse position and the state of the
+    /// left button seen in each frame.
+    fn mouse_frames(harness: &mut TestHarness) -> Vec<((f32, f32), bool)> {
+        let mut frames = Vec::new();
+        let count = harness.run_until_idle(&mut |ui| {
+            frames.push((ui.imgui().mouse_pos(), ui.is_mouse_down(MouseButton::Left)));
+        });
+        assert_eq!(count, frames.len());
+        frames
+    }
+
+    #[test]
+    fn click_at() {
+        let mut harness = TestHarness::new();
+        harness.click_at(100.0, 50.0);
+        assert!(harness.has_pending_input());
+        // The press and the release take one frame each, followed by one idle frame
+        assert_eq!(
+            mouse_frames(&mut harness),
+            vec![((100.0, 50.0), true), ((100.0, 50.0), false), ((100.0, 50.0), false)]
+        );
+        assert!(!harness.has_pending_input());
+
+        harness.click_at(20.0, 30.0);
+        harness.click(MouseButton::Left);
+        let states: Vec<bool> = mouse_frames(&mut harness).iter().map(|f| f.1).collect();
+        assert_eq!(states, vec![true, false, true, false, false]);
+    }
+
+    #[test]
+    fn press_key_with() {
+        let mut harness = TestHarness::new();
+        harness.press_key_with(Key::LeftCtrl, Key::A);
+        let mut frames = Vec::new();
+        harness.run_until_idle(&mut |ui| {
+            frames.push((ui.imgui().key_ctrl(), ui.is_key_down(Key::A)));
+        });
+        // The key is released in the next frame, and the modifier with it
+        assert_eq!(frames, vec![(true, true), (false, false), (false, false)]);
+    }
+
+    #[test]
+    fn type_text() {
+        let mut harness = TestHarness::new();
+        let mut text = ImString::with_capacity(32);
+        let mut input_center = (0.0, 0.0);
+        {
+            let mut text_input = |ui: &Ui| {
+                ui.window("Input")
+                    .position((10.0, 10.0), ImGuiCond::Always)
+                    .build(|| {
+                        ui.input_text("Text", &mut text).build();
+                        let (min, max) = (ui.get_item_rect_min(), ui.get_item_rect_max());
+                        input_center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+                    });
+            };
+            harness.run_frame(&mut text_input);
+        }
+        harness.click_at(input_center.0, input_center.1);
+        harness.type_text("hé!");
+        let mut frames = 0;
+        harness.run_until_idle(&mut |ui| {
+            ui.window("Input")
+                .position((10.0, 10.0), ImGuiCond::Always)
+                .build(|| {
+                    ui.input_text("Text", &mut text).build();
+                });
+            frames += 1;
+        });
+        // Click, release, all characters at once and the idle frame
+        assert_eq!(frames, 4);
+        assert_eq!(text.as_str(), "hé!");
+    }
+
+    #[test]
+    fn run_until_idle_without_input() {
+        let mut harness = TestHarness::new();
+        let mut frames = 0;
+        assert_eq!(harness.run_until_idle(&mut |_| frames += 1), 1);
+        assert_eq!(frames, 1);
+    }
+}
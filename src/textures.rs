@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::os::raw::c_void;
+
+use sys;
+
+/// An opaque texture identifier, which is passed through imgui to the renderer.
+///
+/// Renderers use a `Textures` registry to map the texture ids found in draw commands back to
+/// their own texture objects.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TextureId(usize);
+
+impl TextureId {
+    pub fn new(id: usize) -> TextureId { TextureId(id) }
+    pub fn id(self) -> usize { self.0 }
+    pub(crate) fn as_raw(self) -> sys::ImTextureID { self.0 as *mut c_void }
+}
+
+impl From<usize> for TextureId {
+    fn from(id: usize) -> TextureId { TextureId(id) }
+}
+
+impl From<sys::ImTextureID> for TextureId {
+    fn from(id: sys::ImTextureID) -> TextureId { TextureId(id as usize) }
+}
+
+/// A generic registry of textures, keyed by `TextureId`.
+///
+/// Renderers keep one of these for the font atlas texture and any application textures, and
+/// look up the texture of each draw command from it.
+#[derive(Debug)]
+pub struct Textures<T> {
+    textures: HashMap<usize, T>,
+    next: usize,
+}
+
+impl<T> Default for Textures<T> {
+    fn default() -> Textures<T> { Textures::new() }
+}
+
+impl<T> Textures<T> {
+    pub fn new() -> Textures<T> {
+        Textures {
+            textures: HashMap::new(),
+            next: 0,
+        }
+    }
+    /// Registers a texture and returns a new unique id for it.
+    pub fn insert(&mut self, texture: T) -> TextureId {
+        let id = self.next;
+        self.textures.insert(id, texture);
+        self.next += 1;
+        TextureId(id)
+    }
+    /// Replaces the texture of an existing id, returning the previous texture.
+    ///
+    /// If the id wasn't registered, the texture is registered with that id and `None` is
+    /// returned.
+    pub fn replace(&mut self, id: TextureId, texture: T) -> Option<T> {
+        if id.0 >= self.next {
+            self.next = id.0 + 1;
+        }
+        self.textures.insert(id.0, texture)
+    }
+    /// Removes a texture from the registry, returning it if it was registered.
+    pub fn remove(&mut self, id: TextureId) -> Option<T> { self.textures.remove(&id.0) }
+    pub fn get(&self, id: TextureId) -> Option<&T> { self.textures.get(&id.0) }
+    pub fn get_mut(&mut self, id: TextureId) -> Option<&mut T> { self.textures.get_mut(&id.0) }
+    pub fn contains(&self, id: TextureId) -> bool { self.textures.contains_key(&id.0) }
+    pub fn len(&self) -> usize { self.textures.len() }
+    pub fn is_empty(&self) -> bool { self.textures.is_empty() }
+}
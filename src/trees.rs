@@ -1,7 +1,6 @@
 use sys;
-use std::marker::PhantomData;
 
-use super::{ImGuiCond, ImGuiTreeNodeFlags, Ui};
+use super::{ImGui, ImGuiCond, ImGuiTreeNodeFlags, Ui};
 
 #[must_use]
 pub struct TreeNode<'ui, 'p> {
@@ -9,17 +8,17 @@ pub struct TreeNode<'ui, 'p> {
     label: Option<&'p str>,
     opened: bool,
     opened_cond: ImGuiCond,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> TreeNode<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, id: &'p str) -> Self {
+    pub fn new(ui: &Ui<'ui>, id: &'p str) -> Self {
         TreeNode {
             id: id,
             label: None,
             opened: false,
             opened_cond: ImGuiCond::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -34,6 +33,7 @@ impl<'ui, 'p> TreeNode<'ui, 'p> {
         self
     }
     pub fn build<F: FnOnce()>(self, f: F) {
+        self.imgui.make_current();
         let render = unsafe {
             if !self.opened_cond.is_empty() {
                 sys::igSetNextTreeNodeOpen(self.opened, self.opened_cond);
@@ -48,6 +48,7 @@ impl<'ui, 'p> TreeNode<'ui, 'p> {
         };
         if render {
             f();
+            self.imgui.make_current();
             unsafe { sys::igTreePop() };
         }
     }
@@ -59,15 +60,15 @@ pub struct CollapsingHeader<'ui, 'p> {
     // Some flags are automatically set in ImGui::CollapsingHeader, so
     // we only support a sensible subset here
     flags: ImGuiTreeNodeFlags,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> CollapsingHeader<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, label: &'p str) -> Self {
+    pub fn new(ui: &Ui<'ui>, label: &'p str) -> Self {
         CollapsingHeader {
             label: label,
             flags: ImGuiTreeNodeFlags::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -106,6 +107,7 @@ impl<'ui, 'p> CollapsingHeader<'ui, 'p> {
         self
     }
     pub fn build(self) -> bool {
+        self.imgui.make_current();
         unsafe { sys::igCollapsingHeader(sys::ImStr::from(self.label), self.flags) }
     }
 }
@@ -1,8 +1,8 @@
 use sys;
-use std::marker::PhantomData;
 use std::ptr;
 
-use super::{ImGuiCond, ImGuiWindowFlags, ImVec2, Ui};
+use settings::settings_name;
+use super::{ImGui, ImGuiCond, ImGuiWindowFlags, ImVec2, Ui};
 
 #[must_use]
 pub struct Window<'ui, 'p> {
@@ -13,11 +13,11 @@ pub struct Window<'ui, 'p> {
     name: &'p str,
     opened: Option<&'p mut bool>,
     flags: ImGuiWindowFlags,
-    _phantom: PhantomData<&'ui Ui<'ui>>,
+    imgui: &'ui ImGui,
 }
 
 impl<'ui, 'p> Window<'ui, 'p> {
-    pub fn new(_: &Ui<'ui>, name: &'p str) -> Window<'ui, 'p> {
+    pub fn new(ui: &Ui<'ui>, name: &'p str) -> Window<'ui, 'p> {
         Window {
             pos: (0.0, 0.0),
             pos_cond: ImGuiCond::empty(),
@@ -26,7 +26,7 @@ impl<'ui, 'p> Window<'ui, 'p> {
             name: name,
             opened: None,
             flags: ImGuiWindowFlags::empty(),
-            _phantom: PhantomData,
+            imgui: ui.imgui,
         }
     }
     #[inline]
@@ -149,12 +149,35 @@ impl<'ui, 'p> Window<'ui, 'p> {
         self
     }
     pub fn build<F: FnOnce()>(self, f: F) {
+        self.imgui.make_current();
+        let save_settings = !self.flags.contains(ImGuiWindowFlags::NoSavedSettings);
+        let name = settings_name(self.name);
+        let loaded = if save_settings {
+            self.imgui.settings.borrow_mut().take_pending(name)
+        } else {
+            None
+        };
         let render = unsafe {
-            if !self.pos_cond.is_empty() {
-                sys::igSetNextWindowPos(self.pos.into(), self.pos_cond, ImVec2::zero());
+            // Loaded settings take the place of the conditions except Always, like imgui.ini
+            let (pos_cond, size_cond) = match loaded {
+                Some(ref settings) => {
+                    sys::igSetNextWindowPos(settings.pos.into(), ImGuiCond::Always, ImVec2::zero());
+                    if settings.size != (0.0, 0.0) {
+                        sys::igSetNextWindowSize(settings.size.into(), ImGuiCond::Always);
+                    }
+                    sys::igSetNextWindowCollapsed(settings.collapsed, ImGuiCond::Always);
+                    (
+                        self.pos_cond & ImGuiCond::Always,
+                        self.size_cond & ImGuiCond::Always,
+                    )
+                }
+                None => (self.pos_cond, self.size_cond),
+            };
+            if !pos_cond.is_empty() {
+                sys::igSetNextWindowPos(self.pos.into(), pos_cond, ImVec2::zero());
             }
-            if !self.size_cond.is_empty() {
-                sys::igSetNextWindowSize(self.size.into(), self.size_cond);
+            if !size_cond.is_empty() {
+                sys::igSetNextWindowSize(self.size.into(), size_cond);
             }
             sys::igBegin(
                 sys::ImStr::from(self.name),
@@ -164,8 +187,23 @@ impl<'ui, 'p> Window<'ui, 'p> {
                 self.flags,
             )
         };
+        if save_settings {
+            let (mut pos, mut size) = (ImVec2::zero(), ImVec2::zero());
+            let collapsed = unsafe {
+                sys::igGetWindowPos(&mut pos);
+                sys::igGetWindowSize(&mut size);
+                sys::igIsWindowCollapsed()
+            };
+            self.imgui.settings.borrow_mut().record(
+                name,
+                (pos.x, pos.y),
+                (size.x, size.y),
+                collapsed,
+            );
+        }
         if render {
             f();
+            self.imgui.make_current();
         }
         unsafe { sys::igEnd() };
     }
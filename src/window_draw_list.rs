@@ -0,0 +1,853 @@
+#![warn(missing_docs)]
+use sys;
+use sys::{ImColor, ImDrawCornerFlags, ImDrawList, ImU32};
+use std::os::raw::{c_int, c_void};
+
+use super::{ImGui, ImVec2, TextureId, Ui};
+use render::{draw_callback_marker, reset_render_state_data};
+
+/// The draw list of the current window, for custom drawing between widgets.
+///
+/// Shapes are added in window coordinates relative to the screen, so the position of the
+/// next widget (`Ui::get_cursor_screen_pos`) is usually a good starting point.
+pub struct WindowDrawList<'ui> {
+    draw_list: *mut ImDrawList,
+    imgui: &'ui ImGui,
+}
+
+impl<'ui> WindowDrawList<'ui> {
+    pub(crate) fn new(ui: &Ui<'ui>) -> Self {
+        ui.imgui.make_current();
+        WindowDrawList {
+            draw_list: unsafe { sys::igGetWindowDrawList() },
+            imgui: ui.imgui,
+        }
+    }
+
+    /// Returns a line from `p1` to `p2` with the given color.
+    pub fn add_line<'dl, P1, P2, C>(&'dl self, p1: P1, p2: P2, color: C) -> Line<'ui, 'dl>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Line {
+            p1: p1.into(),
+            p2: p2.into(),
+            color: color.into(),
+            thickness: 1.0,
+            draw_list: self,
+        }
+    }
+
+    /// Returns a rectangle with the upper left corner `p1` and the lower right corner `p2`.
+    pub fn add_rect<'dl, P1, P2, C>(&'dl self, p1: P1, p2: P2, color: C) -> Rect<'ui, 'dl>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Rect {
+            p1: p1.into(),
+            p2: p2.into(),
+            color: color.into(),
+            rounding: 0.0,
+            corners: ImDrawCornerFlags::All,
+            thickness: 1.0,
+            filled: false,
+            draw_list: self,
+        }
+    }
+
+    /// Draws a filled rectangle with a different color in each corner, which are blended
+    /// across the rectangle.
+    pub fn add_rect_filled_multicolor<P1, P2, C1, C2, C3, C4>(
+        &self,
+        p1: P1,
+        p2: P2,
+        col_upr_left: C1,
+        col_upr_right: C2,
+        col_bot_right: C3,
+        col_bot_left: C4,
+    ) where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        C1: Into<ImColor>,
+        C2: Into<ImColor>,
+        C3: Into<ImColor>,
+        C4: Into<ImColor>,
+    {
+        self.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_AddRectFilledMultiColor(
+                self.draw_list,
+                p1.into(),
+                p2.into(),
+                col_upr_left.into().into(),
+                col_upr_right.into().into(),
+                col_bot_right.into().into(),
+                col_bot_left.into().into(),
+            );
+        }
+    }
+
+    /// Returns a triangle with the given corners.
+    pub fn add_triangle<'dl, P1, P2, P3, C>(
+        &'dl self,
+        p1: P1,
+        p2: P2,
+        p3: P3,
+        color: C,
+    ) -> Triangle<'ui, 'dl>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Triangle {
+            p1: p1.into(),
+            p2: p2.into(),
+            p3: p3.into(),
+            color: color.into(),
+            thickness: 1.0,
+            filled: false,
+            draw_list: self,
+        }
+    }
+
+    /// Returns a circle with the given center and radius.
+    pub fn add_circle<'dl, P, C>(
+        &'dl self,
+        center: P,
+        radius: f32,
+        color: C,
+    ) -> Circle<'ui, 'dl>
+    where
+        P: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Circle {
+            center: center.into(),
+            radius: radius,
+            color: color.into(),
+            num_segments: 12,
+            filled: false,
+            draw_list: self,
+        }
+    }
+
+    /// Returns a cubic bezier curve from `pos0` to `pos1` with the control points `cp0` and
+    /// `cp1`.
+    pub fn add_bezier_curve<'dl, P1, P2, P3, P4, C>(
+        &'dl self,
+        pos0: P1,
+        cp0: P2,
+        cp1: P3,
+        pos1: P4,
+        color: C,
+    ) -> BezierCurve<'ui, 'dl>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        P4: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        BezierCurve {
+            pos0: pos0.into(),
+            cp0: cp0.into(),
+            cp1: cp1.into(),
+            pos1: pos1.into(),
+            color: color.into(),
+            thickness: 1.0,
+            num_segments: 0,
+            draw_list: self,
+        }
+    }
+
+    /// Returns a line through all the given points.
+    pub fn add_polyline<'dl, P, C>(&'dl self, points: &[P], color: C) -> PolyLine<'ui, 'dl>
+    where
+        P: Into<ImVec2> + Copy,
+        C: Into<ImColor>,
+    {
+        PolyLine {
+            points: points.iter().map(|&p| p.into()).collect(),
+            color: color.into(),
+            closed: false,
+            thickness: 1.0,
+            anti_aliased: true,
+            draw_list: self,
+        }
+    }
+
+    /// Draws a filled convex polygon with the given points.
+    pub fn add_convex_poly_filled<P, C>(&self, points: &[P], color: C)
+    where
+        P: Into<ImVec2> + Copy,
+        C: Into<ImColor>,
+    {
+        self.imgui.make_current();
+        let points: Vec<ImVec2> = points.iter().map(|&p| p.into()).collect();
+        unsafe {
+            sys::ImDrawList_AddConvexPolyFilled(
+                self.draw_list,
+                points.as_ptr(),
+                points.len() as c_int,
+                color.into().into(),
+                true,
+            );
+        }
+    }
+
+    /// Draws text with the current font at the given position.
+    pub fn add_text<'p, P, C>(&self, pos: P, color: C, text: &'p str)
+    where
+        P: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        // The current font of the context is used
+        self.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_AddText(
+                self.draw_list,
+                pos.into(),
+                color.into().into(),
+                sys::ImStr::from(text),
+            );
+        }
+    }
+
+    /// Returns an image that covers the rectangle from `p_min` to `p_max`.
+    pub fn add_image<'dl, P1, P2>(
+        &'dl self,
+        texture_id: TextureId,
+        p_min: P1,
+        p_max: P2,
+    ) -> DrawListImage<'ui, 'dl>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+    {
+        DrawListImage {
+            texture_id: texture_id,
+            p_min: p_min.into(),
+            p_max: p_max.into(),
+            uv_min: ImVec2::new(0.0, 0.0),
+            uv_max: ImVec2::new(1.0, 1.0),
+            color: ImColor::new(1.0, 1.0, 1.0, 1.0),
+            draw_list: self,
+        }
+    }
+
+    /// Starts building a path, which is drawn with `Path::fill` or `Path::stroke`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// let draw_list = ui.get_window_draw_list();
+    /// draw_list
+    ///     .path()
+    ///     .line_to((10.0, 10.0))
+    ///     .bezier_curve_to((50.0, 10.0), (50.0, 90.0), (90.0, 90.0))
+    ///     .stroke([1.0, 0.5, 0.0], false, 2.0);
+    /// ```
+    pub fn path<'dl>(&'dl self) -> Path<'ui, 'dl> {
+        self.imgui.make_current();
+        unsafe { sys::ImDrawList_PathClear(self.draw_list) };
+        Path { draw_list: self }
+    }
+
+    /// Splits the draw list into `channels_count` channels, and runs a function that can switch
+    /// between them. The channels are merged afterwards, in order, so everything drawn to
+    /// channel 0 ends up behind everything drawn to channel 1 and so on. Drawing starts in
+    /// channel 0. The channels are also merged if the function panics.
+    ///
+    /// # Panics
+    /// Panics if `channels_count` is zero, or if the draw list is already split.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// let draw_list = ui.get_window_draw_list();
+    /// draw_list.channels_split(2, |channels| {
+    ///     channels.set_current(1);
+    ///     // Foreground: nodes
+    ///     draw_list.add_rect((10.0, 10.0), (50.0, 30.0), [0.3, 0.3, 0.3]).filled(true).build();
+    ///     channels.set_current(0);
+    ///     // Background: connections, drawn behind the nodes
+    ///     draw_list.add_line((30.0, 20.0), (90.0, 60.0), [1.0, 1.0, 0.0]).build();
+    /// });
+    /// ```
+    pub fn channels_split<F: FnOnce(&ChannelsSplit)>(&self, channels_count: u32, f: F) {
+        assert!(channels_count > 0, "channels_count must be at least 1");
+        assert!(
+            unsafe { (*self.draw_list).channels_count } <= 1,
+            "The window draw list is already split into channels"
+        );
+        self.imgui.make_current();
+        unsafe { sys::ImDrawList_ChannelsSplit(self.draw_list, channels_count as c_int) };
+        // Dropping the split merges the channels, also if f panics
+        let channels = ChannelsSplit {
+            draw_list: self,
+            channels_count: channels_count,
+        };
+        f(&channels);
+    }
+
+    /// Adds a callback, which the renderer runs when it reaches this point of the draw list.
+    ///
+    /// The callback receives the current clip rectangle as `[x1, y1, x2, y2]` in display
+    /// coordinates. It can be used e.g. to render a 3D viewport with the graphics API, or to
+    /// change the render state for the following commands. Callbacks are dropped when the
+    /// next frame starts.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame((0, 0), (0, 0), 0.1);
+    /// let draw_list = ui.get_window_draw_list();
+    /// draw_list.add_callback(|clip_rect| {
+    ///     println!("Render the scene into {:?}", clip_rect);
+    /// });
+    /// draw_list.add_reset_render_state();
+    /// ```
+    pub fn add_callback<F: FnMut([f32; 4]) + 'static>(&self, callback: F) {
+        self.imgui.make_current();
+        let mut callbacks = self.imgui.draw_callbacks.borrow_mut();
+        let index = callbacks.len();
+        callbacks.push(Box::new(callback));
+        unsafe {
+            sys::ImDrawList_AddCallback(
+                self.draw_list,
+                Some(draw_callback_marker),
+                index as *mut c_void,
+            );
+        }
+    }
+
+    /// Asks the renderer to restore its own render state, e.g. after a callback has changed
+    /// it.
+    pub fn add_reset_render_state(&self) {
+        self.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_AddCallback(
+                self.draw_list,
+                Some(draw_callback_marker),
+                reset_render_state_data(),
+            );
+        }
+    }
+
+    /// Runs a function with a clip rectangle pushed to the draw list. The rectangle is
+    /// intersected with the current clip rectangle.
+    pub fn with_clip_rect<P1, P2, F>(&self, min: P1, max: P2, f: F)
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        F: FnOnce(),
+    {
+        self.imgui.make_current();
+        unsafe { sys::ImDrawList_PushClipRect(self.draw_list, min.into(), max.into(), true) };
+        f();
+        self.imgui.make_current();
+        unsafe { sys::ImDrawList_PopClipRect(self.draw_list) };
+    }
+}
+
+/// The channels of a split draw list, see `WindowDrawList::channels_split`.
+pub struct ChannelsSplit<'ui: 'dl, 'dl> {
+    draw_list: &'dl WindowDrawList<'ui>,
+    channels_count: u32,
+}
+
+impl<'ui, 'dl> ChannelsSplit<'ui, 'dl> {
+    /// Switches to the given channel. Everything drawn to the window draw list afterwards goes
+    /// to this channel.
+    ///
+    /// # Panics
+    /// Panics if the channel index is out of range.
+    pub fn set_current(&self, channel_index: u32) {
+        assert!(
+            channel_index < self.channels_count,
+            "Channel index {} out of range, there are {} channels",
+            channel_index,
+            self.channels_count
+        );
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_ChannelsSetCurrent(self.draw_list.draw_list, channel_index as c_int)
+        };
+    }
+
+    /// Returns the number of channels.
+    pub fn channels_count(&self) -> u32 { self.channels_count }
+}
+
+impl<'ui, 'dl> Drop for ChannelsSplit<'ui, 'dl> {
+    fn drop(&mut self) {
+        self.draw_list.imgui.make_current();
+        unsafe { sys::ImDrawList_ChannelsMerge(self.draw_list.draw_list) };
+    }
+}
+
+/// A line, added with `WindowDrawList::add_line`.
+#[must_use = "should call .build() to draw the object"]
+pub struct Line<'ui: 'dl, 'dl> {
+    p1: ImVec2,
+    p2: ImVec2,
+    color: ImColor,
+    thickness: f32,
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> Line<'ui, 'dl> {
+    /// Sets the thickness of the line (default: 1.0).
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Draws the line.
+    pub fn build(self) {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_AddLine(
+                self.draw_list.draw_list,
+                self.p1,
+                self.p2,
+                self.color.into(),
+                self.thickness,
+            );
+        }
+    }
+}
+
+/// A rectangle, added with `WindowDrawList::add_rect`.
+#[must_use = "should call .build() to draw the object"]
+pub struct Rect<'ui: 'dl, 'dl> {
+    p1: ImVec2,
+    p2: ImVec2,
+    color: ImColor,
+    rounding: f32,
+    corners: ImDrawCornerFlags,
+    thickness: f32,
+    filled: bool,
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> Rect<'ui, 'dl> {
+    /// Sets the corner rounding radius (default: 0.0).
+    #[inline]
+    pub fn rounding(mut self, rounding: f32) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets which corners are rounded (default: all).
+    #[inline]
+    pub fn corners(mut self, corners: ImDrawCornerFlags) -> Self {
+        self.corners = corners;
+        self
+    }
+
+    /// Sets the thickness of the outline (default: 1.0). Ignored for filled rectangles.
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets whether the rectangle is filled (default: false).
+    #[inline]
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draws the rectangle.
+    pub fn build(self) {
+        self.draw_list.imgui.make_current();
+        let draw_list = self.draw_list.draw_list;
+        let color: ImU32 = self.color.into();
+        unsafe {
+            if self.filled {
+                sys::ImDrawList_AddRectFilled(
+                    draw_list,
+                    self.p1,
+                    self.p2,
+                    color,
+                    self.rounding,
+                    self.corners.bits(),
+                );
+            } else {
+                sys::ImDrawList_AddRect(
+                    draw_list,
+                    self.p1,
+                    self.p2,
+                    color,
+                    self.rounding,
+                    self.corners.bits(),
+                    self.thickness,
+                );
+            }
+        }
+    }
+}
+
+/// A triangle, added with `WindowDrawList::add_triangle`.
+#[must_use = "should call .build() to draw the object"]
+pub struct Triangle<'ui: 'dl, 'dl> {
+    p1: ImVec2,
+    p2: ImVec2,
+    p3: ImVec2,
+    color: ImColor,
+    thickness: f32,
+    filled: bool,
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> Triangle<'ui, 'dl> {
+    /// Sets the thickness of the outline (default: 1.0). Ignored for filled triangles.
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets whether the triangle is filled (default: false).
+    #[inline]
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draws the triangle.
+    pub fn build(self) {
+        self.draw_list.imgui.make_current();
+        let draw_list = self.draw_list.draw_list;
+        let color: ImU32 = self.color.into();
+        unsafe {
+            if self.filled {
+                sys::ImDrawList_AddTriangleFilled(draw_list, self.p1, self.p2, self.p3, color);
+            } else {
+                sys::ImDrawList_AddTriangle(
+                    draw_list,
+                    self.p1,
+                    self.p2,
+                    self.p3,
+                    color,
+                    self.thickness,
+                );
+            }
+        }
+    }
+}
+
+/// A circle, added with `WindowDrawList::add_circle`.
+#[must_use = "should call .build() to draw the object"]
+pub struct Circle<'ui: 'dl, 'dl> {
+    center: ImVec2,
+    radius: f32,
+    color: ImColor,
+    num_segments: i32,
+    filled: bool,
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> Circle<'ui, 'dl> {
+    /// Sets the number of line segments the circle is made of (default: 12).
+    #[inline]
+    pub fn num_segments(mut self, num_segments: i32) -> Self {
+        self.num_segments = num_segments;
+        self
+    }
+
+    /// Sets whether the circle is filled (default: false).
+    #[inline]
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draws the circle.
+    pub fn build(self) {
+        self.draw_list.imgui.make_current();
+        let draw_list = self.draw_list.draw_list;
+        let color: ImU32 = self.color.into();
+        unsafe {
+            if self.filled {
+                sys::ImDrawList_AddCircleFilled(
+                    draw_list,
+                    self.center,
+                    self.radius,
+                    color,
+                    self.num_segments as c_int,
+                );
+            } else {
+                sys::ImDrawList_AddCircle(
+                    draw_list,
+                    self.center,
+                    self.radius,
+                    color,
+                    self.num_segments as c_int,
+                );
+            }
+        }
+    }
+}
+
+/// A cubic bezier curve, added with `WindowDrawList::add_bezier_curve`.
+#[must_use = "should call .build() to draw the object"]
+pub struct BezierCurve<'ui: 'dl, 'dl> {
+    pos0: ImVec2,
+    cp0: ImVec2,
+    cp1: ImVec2,
+    pos1: ImVec2,
+    color: ImColor,
+    thickness: f32,
+    num_segments: i32,
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> BezierCurve<'ui, 'dl> {
+    /// Sets the thickness of the curve (default: 1.0).
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the number of line segments the curve is made of. Zero picks a number
+    /// automatically (default).
+    #[inline]
+    pub fn num_segments(mut self, num_segments: i32) -> Self {
+        self.num_segments = num_segments;
+        self
+    }
+
+    /// Draws the curve.
+    pub fn build(self) {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_AddBezierCurve(
+                self.draw_list.draw_list,
+                self.pos0,
+                self.cp0,
+                self.cp1,
+                self.pos1,
+                self.color.into(),
+                self.thickness,
+                self.num_segments as c_int,
+            );
+        }
+    }
+}
+
+/// A line through several points, added with `WindowDrawList::add_polyline`.
+#[must_use = "should call .build() to draw the object"]
+pub struct PolyLine<'ui: 'dl, 'dl> {
+    points: Vec<ImVec2>,
+    color: ImColor,
+    closed: bool,
+    thickness: f32,
+    anti_aliased: bool,
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> PolyLine<'ui, 'dl> {
+    /// Sets whether the last point is connected to the first one (default: false).
+    #[inline]
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Sets the thickness of the line (default: 1.0).
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets whether the line is anti-aliased (default: true).
+    #[inline]
+    pub fn anti_aliased(mut self, anti_aliased: bool) -> Self {
+        self.anti_aliased = anti_aliased;
+        self
+    }
+
+    /// Draws the line.
+    pub fn build(self) {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_AddPolyLine(
+                self.draw_list.draw_list,
+                self.points.as_ptr(),
+                self.points.len() as c_int,
+                self.color.into(),
+                self.closed,
+                self.thickness,
+                self.anti_aliased,
+            );
+        }
+    }
+}
+
+/// An image, added with `WindowDrawList::add_image`.
+#[must_use = "should call .build() to draw the object"]
+pub struct DrawListImage<'ui: 'dl, 'dl> {
+    texture_id: TextureId,
+    p_min: ImVec2,
+    p_max: ImVec2,
+    uv_min: ImVec2,
+    uv_max: ImVec2,
+    color: ImColor,
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> DrawListImage<'ui, 'dl> {
+    /// Sets the texture coordinates of the upper left corner (default: `[0.0, 0.0]`).
+    #[inline]
+    pub fn uv_min<P: Into<ImVec2>>(mut self, uv_min: P) -> Self {
+        self.uv_min = uv_min.into();
+        self
+    }
+
+    /// Sets the texture coordinates of the lower right corner (default: `[1.0, 1.0]`).
+    #[inline]
+    pub fn uv_max<P: Into<ImVec2>>(mut self, uv_max: P) -> Self {
+        self.uv_max = uv_max.into();
+        self
+    }
+
+    /// Sets the color the texture is multiplied with (default: opaque white).
+    #[inline]
+    pub fn color<C: Into<ImColor>>(mut self, color: C) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Draws the image.
+    pub fn build(self) {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_AddImage(
+                self.draw_list.draw_list,
+                self.texture_id.as_raw(),
+                self.p_min,
+                self.p_max,
+                self.uv_min,
+                self.uv_max,
+                self.color.into(),
+            );
+        }
+    }
+}
+
+/// A path, started with `WindowDrawList::path`.
+///
+/// The path is discarded if it's dropped without calling `fill` or `stroke`.
+#[must_use = "should call .fill() or .stroke() to draw the path"]
+pub struct Path<'ui: 'dl, 'dl> {
+    draw_list: &'dl WindowDrawList<'ui>,
+}
+
+impl<'ui, 'dl> Path<'ui, 'dl> {
+    /// Adds a line from the previous point to `pos`.
+    pub fn line_to<P: Into<ImVec2>>(self, pos: P) -> Self {
+        self.draw_list.imgui.make_current();
+        unsafe { sys::ImDrawList_PathLineTo(self.draw_list.draw_list, pos.into()) };
+        self
+    }
+
+    /// Adds an arc around `center`. The angles are in radians.
+    pub fn arc_to<P: Into<ImVec2>>(self, center: P, radius: f32, a_min: f32, a_max: f32) -> Self {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_PathArcTo(
+                self.draw_list.draw_list,
+                center.into(),
+                radius,
+                a_min,
+                a_max,
+                10,
+            );
+        }
+        self
+    }
+
+    /// Adds a cubic bezier curve from the previous point to `p3`, with the control points `p1`
+    /// and `p2`.
+    pub fn bezier_curve_to<P1, P2, P3>(self, p1: P1, p2: P2, p3: P3) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+    {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_PathBezierCurveTo(
+                self.draw_list.draw_list,
+                p1.into(),
+                p2.into(),
+                p3.into(),
+                0,
+            );
+        }
+        self
+    }
+
+    /// Adds a rectangle with the given corner rounding.
+    pub fn rect<P1, P2>(self, rect_min: P1, rect_max: P2, rounding: f32) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+    {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_PathRect(
+                self.draw_list.draw_list,
+                rect_min.into(),
+                rect_max.into(),
+                rounding,
+                ImDrawCornerFlags::All.bits(),
+            );
+        }
+        self
+    }
+
+    /// Fills the path, which must be convex.
+    pub fn fill<C: Into<ImColor>>(self, color: C) {
+        self.draw_list.imgui.make_current();
+        unsafe { sys::ImDrawList_PathFillConvex(self.draw_list.draw_list, color.into().into()) };
+    }
+
+    /// Draws a line along the path. If `closed` is true, the last point is connected to the
+    /// first one.
+    pub fn stroke<C: Into<ImColor>>(self, color: C, closed: bool, thickness: f32) {
+        self.draw_list.imgui.make_current();
+        unsafe {
+            sys::ImDrawList_PathStroke(
+                self.draw_list.draw_list,
+                color.into().into(),
+                closed,
+                thickness,
+            );
+        }
+    }
+}
+
+impl<'ui, 'dl> Drop for Path<'ui, 'dl> {
+    fn drop(&mut self) {
+        self.draw_list.imgui.make_current();
+        unsafe { sys::ImDrawList_PathClear(self.draw_list.draw_list) };
+    }
+}